@@ -0,0 +1,363 @@
+//! Reads game assets as saved to JSON by FModel or CUE4Parse ("Save Properties"): every file holds
+//! an array of exports with their `Type`, `Name` and `Properties`, or `StringTable` for string
+//! tables. Property names are the ones mirrored by the structs in `data.rs`, in PascalCase.
+//!
+//! | Export                      | Read                                                   |
+//! |-----------------------------|--------------------------------------------------------|
+//! | `MissionSetup`              | `MissionTemplates`, each a `MissionTemplate`, `Rarity` |
+//! | `MissionTemplate`           | `PrimaryObjective`, `SecondaryObjectives`,             |
+//! |                             | `DeepDiveObjectives`, `DNA`                            |
+//! | `MissionDNA`                | `Duration`, `Complexity`, `Weight`                     |
+//! | `DeepDiveSettings`          | `Biomes`, `Mutators`, `Warnings`                       |
+//! | `DeepDiveTemplate`, by name | `Missions`, `MutatorCount`, `WarningCount`             |
+//! | `StringTable`, by name      | the codename lists, in entry order                     |
+//!
+//! Objective requirements are not part of these assets and are taken from a base export instead.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use serde::de::{DeserializeOwned, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+use crate::export::{self, Export};
+
+/// Names of the exports that can not be told apart by their type.
+#[derive(Debug, Clone)]
+pub struct AssetNames {
+    pub normal_template: String,
+    pub hard_template: String,
+    pub names_first: String,
+    pub names_last: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssetExport {
+    #[serde(rename = "Type")]
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub properties: serde_json::Value,
+    #[serde(default)]
+    pub string_table: Option<StringTable>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StringTable {
+    pub keys_to_entries: Entries,
+}
+
+/// String table entries in the order of the file.
+#[derive(Debug)]
+pub struct Entries(pub Vec<String>);
+
+impl<'de> Deserialize<'de> for Entries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = Entries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of string table keys to entries")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Entries, A::Error> {
+                let mut entries = vec![];
+                while let Some((_, entry)) = map.next_entry::<String, String>()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// A reference to an asset, a class or an enum value, reduced to the name `data.rs` uses for it.
+///
+/// Accepts object references (`ObjectName`/`ObjectPath`), soft object paths (`AssetPathName`),
+/// plain paths and `EEnum::Value` strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference(pub String);
+
+impl<'de> Deserialize<'de> for Reference {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Path(String),
+            Object {
+                #[serde(rename = "ObjectPath")]
+                path: String,
+            },
+            Soft {
+                #[serde(rename = "AssetPathName")]
+                path: String,
+            },
+        }
+
+        let path = match Raw::deserialize(deserializer)? {
+            Raw::Path(path) | Raw::Object { path } | Raw::Soft { path } => path,
+        };
+        Ok(Reference(asset_name(&path).to_string()))
+    }
+}
+
+/// Asset name of a path such as `/Game/Objectives/OBJ_1st_Extraction.OBJ_1st_Extraction_C` or
+/// `FSD/Content/Objectives/OBJ_1st_Extraction.1`. Blueprint classes are named after their asset,
+/// so the `_C` of the class does not need to be stripped.
+fn asset_name(path: &str) -> &str {
+    if let Some((_, value)) = path.rsplit_once("::") {
+        return value;
+    }
+    let file = path.rsplit('/').next().unwrap_or(path);
+    file.split('.').next().unwrap_or(file)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MissionSetup {
+    mission_templates: Vec<MissionTemplateItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MissionTemplateItem {
+    mission_template: Reference,
+    rarity: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MissionTemplate {
+    primary_objective: Reference,
+    #[serde(default)]
+    secondary_objectives: Vec<Reference>,
+    #[serde(default)]
+    deep_dive_objectives: Vec<Reference>,
+    #[serde(rename = "DNA", alias = "MissionDNA")]
+    dna: Vec<Reference>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MissionDna {
+    duration: Reference,
+    complexity: Reference,
+    weight: f32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeepDiveSettings {
+    biomes: Vec<Reference>,
+    mutators: Vec<Reference>,
+    warnings: Vec<Reference>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeepDiveTemplate {
+    missions: Vec<DeepDiveTemplateItem>,
+    mutator_count: RandInterval,
+    warning_count: RandInterval,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RandInterval {
+    intervals: Vec<RandIntervalItem>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RandIntervalItem {
+    weight: f32,
+    range: IRandRange,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IRandRange {
+    min: i32,
+    max: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeepDiveTemplateItem {
+    mission: Reference,
+    probability: u32,
+    #[serde(default)]
+    allowed_durations: Vec<Reference>,
+    #[serde(default)]
+    allowed_complexities: Vec<Reference>,
+    #[serde(default, alias = "bCanOnlyAppearOnce")]
+    can_only_appear_once: bool,
+    #[serde(default, alias = "bCanOnlyAppearOncePerDeepDiveSet")]
+    can_only_appear_once_per_deep_dive_set: bool,
+}
+
+/// Reads every `.json` file below `dir`.
+pub fn read_dir(dir: &Path) -> anyhow::Result<Vec<AssetExport>> {
+    let mut exports = vec![];
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.path());
+    for entry in entries {
+        let path = entry.path();
+        if path.is_dir() {
+            exports.extend(read_dir(&path)?);
+        } else if path.extension().is_some_and(|e| e == "json") {
+            let json = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            let file: Vec<AssetExport> = serde_json::from_str(&json)
+                .with_context(|| format!("parsing {}", path.display()))?;
+            exports.extend(file);
+        }
+    }
+    Ok(exports)
+}
+
+/// Builds an export from the game assets. DNA requirements are copied from `base`.
+pub fn to_export(
+    assets: &[AssetExport],
+    names: &AssetNames,
+    base: Option<&Export>,
+) -> anyhow::Result<Export> {
+    let setup: MissionSetup = single(assets, "MissionSetup")?;
+    let settings: DeepDiveSettings = single(assets, "DeepDiveSettings")?;
+
+    let mut templates = BTreeMap::new();
+    for (name, template) in of_type::<MissionTemplate>(assets, "MissionTemplate")? {
+        templates.insert(name, template);
+    }
+    let mut mission_templates = BTreeMap::new();
+    for item in setup.mission_templates {
+        let name = item.mission_template.0;
+        let template = templates
+            .remove(&name)
+            .with_context(|| format!("mission template {name} is not among the assets"))?;
+        let template = export::MissionTemplate {
+            rarity: item.rarity,
+            primary_objective: template.primary_objective.0,
+            secondary_objectives: names_of(template.secondary_objectives),
+            deep_dive_objectives: names_of(template.deep_dive_objectives),
+            dna: names_of(template.dna),
+        };
+        mission_templates.insert(name, template);
+    }
+
+    let dna = of_type::<MissionDna>(assets, "MissionDNA")?
+        .into_iter()
+        .map(|(name, dna)| {
+            let requirement = base
+                .and_then(|b| b.dna.get(&name))
+                .and_then(|d| d.requirement.clone());
+            let dna = export::Dna {
+                duration: dna.duration.0,
+                complexity: dna.complexity.0,
+                weight: dna.weight,
+                requirement,
+            };
+            (name, dna)
+        })
+        .collect();
+
+    Ok(Export {
+        biomes: names_of(settings.biomes),
+        mission_templates,
+        dna,
+        normal_template: deep_dive_template(assets, &names.normal_template)?,
+        hard_template: deep_dive_template(assets, &names.hard_template)?,
+        deep_dive_mutators: names_of(settings.mutators),
+        deep_dive_warnings: names_of(settings.warnings),
+        names_first: string_table(assets, &names.names_first)?,
+        names_last: string_table(assets, &names.names_last)?,
+    })
+}
+
+fn of_type<T: DeserializeOwned>(
+    assets: &[AssetExport],
+    kind: &str,
+) -> anyhow::Result<Vec<(String, T)>> {
+    assets
+        .iter()
+        .filter(|a| a.kind == kind)
+        .map(|a| Ok((a.name.clone(), properties(a)?)))
+        .collect()
+}
+
+fn single<T: DeserializeOwned>(assets: &[AssetExport], kind: &str) -> anyhow::Result<T> {
+    let mut found = of_type(assets, kind)?;
+    match found.len() {
+        0 => bail!("no {kind} export among the assets"),
+        1 => Ok(found.pop().unwrap().1),
+        n => bail!("{n} {kind} exports among the assets, expected one"),
+    }
+}
+
+fn named<'a>(assets: &'a [AssetExport], kind: &str, name: &str) -> anyhow::Result<&'a AssetExport> {
+    assets
+        .iter()
+        .find(|a| a.kind == kind && a.name == name)
+        .with_context(|| format!("no {kind} export named {name} among the assets"))
+}
+
+fn properties<T: DeserializeOwned>(asset: &AssetExport) -> anyhow::Result<T> {
+    T::deserialize(&asset.properties)
+        .with_context(|| format!("reading the properties of {} {}", asset.kind, asset.name))
+}
+
+fn deep_dive_template(
+    assets: &[AssetExport],
+    name: &str,
+) -> anyhow::Result<export::DeepDiveTemplate> {
+    let template: DeepDiveTemplate = properties(named(assets, "DeepDiveTemplate", name)?)?;
+    let intervals = |interval: RandInterval| {
+        interval
+            .intervals
+            .into_iter()
+            .map(|i| export::RandInterval {
+                weight: i.weight,
+                min: i.range.min,
+                max: i.range.max,
+            })
+            .collect()
+    };
+    Ok(export::DeepDiveTemplate {
+        mutator_count: intervals(template.mutator_count),
+        warning_count: intervals(template.warning_count),
+        missions: template
+            .missions
+            .into_iter()
+            .map(|m| export::DeepDiveMission {
+                mission: m.mission.0,
+                probability: m.probability,
+                allowed_durations: names_of(m.allowed_durations),
+                allowed_complexities: names_of(m.allowed_complexities),
+                can_only_appear_once: m.can_only_appear_once,
+                can_only_appear_once_per_deep_dive_set: m.can_only_appear_once_per_deep_dive_set,
+            })
+            .collect(),
+    })
+}
+
+fn string_table(assets: &[AssetExport], name: &str) -> anyhow::Result<Vec<String>> {
+    let table = named(assets, "StringTable", name)?;
+    match &table.string_table {
+        Some(table) => Ok(table.keys_to_entries.0.clone()),
+        None => bail!("string table {name} has no entries"),
+    }
+}
+
+fn names_of(references: Vec<Reference>) -> Vec<String> {
+    references.into_iter().map(|r| r.0).collect()
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// Asset export in the layout of a `drg_mission_gen_core` data pack. Names are kept as strings so
+/// variants the core crate does not know about yet can still be read and reported.
+#[derive(Debug, Deserialize)]
+pub struct Export {
+    pub biomes: Vec<String>,
+    pub mission_templates: BTreeMap<String, MissionTemplate>,
+    pub dna: BTreeMap<String, Dna>,
+    pub normal_template: DeepDiveTemplate,
+    pub hard_template: DeepDiveTemplate,
+    #[serde(default)]
+    pub deep_dive_mutators: Vec<String>,
+    #[serde(default)]
+    pub deep_dive_warnings: Vec<String>,
+    pub names_first: Vec<String>,
+    pub names_last: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MissionTemplate {
+    pub rarity: f32,
+    pub primary_objective: String,
+    pub secondary_objectives: Vec<String>,
+    pub deep_dive_objectives: Vec<String>,
+    pub dna: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Dna {
+    pub duration: String,
+    pub complexity: String,
+    pub weight: f32,
+    #[serde(default)]
+    pub requirement: Option<Requirement>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Requirement {
+    pub resource: String,
+    pub amount: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeepDiveTemplate {
+    pub mutator_count: Vec<RandInterval>,
+    pub warning_count: Vec<RandInterval>,
+    pub missions: Vec<DeepDiveMission>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RandInterval {
+    pub weight: f32,
+    pub min: i32,
+    pub max: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeepDiveMission {
+    pub mission: String,
+    pub probability: u32,
+    #[serde(default)]
+    pub allowed_durations: Vec<String>,
+    #[serde(default)]
+    pub allowed_complexities: Vec<String>,
+    #[serde(default)]
+    pub can_only_appear_once: bool,
+    #[serde(default)]
+    pub can_only_appear_once_per_deep_dive_set: bool,
+}
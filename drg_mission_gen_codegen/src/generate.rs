@@ -0,0 +1,143 @@
+//! Emits the generated regions of `data.rs`. Output is unformatted and run through rustfmt
+//! afterwards.
+
+use std::fmt::Write;
+
+use crate::export::{DeepDiveTemplate, Export, RandInterval};
+
+/// Generates `EMissionTemplate::get`, with match arms in `order`.
+pub fn mission_templates(export: &Export, order: &[String]) -> String {
+    let mut arms = String::new();
+    for name in order {
+        let template = &export.mission_templates[name];
+        writeln!(
+            arms,
+            "EMissionTemplate::{name} => &FMissionTemplateItem {{
+                mission_template: UMissionTemplate {{
+                    primary_objective: EObjective::{},
+                    secondary_objectives: {},
+                    deep_dive_objectives: {},
+                    dna: {},
+                }},
+                rarity: {:?},
+            }},",
+            template.primary_objective,
+            slice("EObjective", &template.secondary_objectives),
+            slice("EObjective", &template.deep_dive_objectives),
+            slice("EMissionDNA", &template.dna),
+            template.rarity,
+        )
+        .unwrap();
+    }
+    format!(
+        "impl EMissionTemplate {{
+            pub fn get(&self) -> &'static FMissionTemplateItem {{
+                match self {{ {arms} }}
+            }}
+        }}"
+    )
+}
+
+/// Generates `EMissionDNA::get`, with match arms in `order`.
+pub fn mission_dna(export: &Export, order: &[String]) -> String {
+    let mut arms = String::new();
+    for name in order {
+        let dna = &export.dna[name];
+        let requirement = match &dna.requirement {
+            Some(r) => format!(
+                "Some(ObjectiveRequirement {{ resource: Resource::{}, amount: {} }})",
+                r.resource, r.amount
+            ),
+            None => "None".to_string(),
+        };
+        writeln!(
+            arms,
+            "EMissionDNA::{name} => &UMissionDNA {{
+                duration: EMissionDuration::{},
+                complexity: EMissionComplexity::{},
+                weight: {:?},
+                requirement: {requirement},
+            }},",
+            dna.duration, dna.complexity, dna.weight,
+        )
+        .unwrap();
+    }
+    format!(
+        "impl EMissionDNA {{
+            pub fn get(self) -> &'static UMissionDNA {{
+                match self {{ {arms} }}
+            }}
+        }}"
+    )
+}
+
+/// Generates a Deep Dive template getter such as `get_normal_template`.
+pub fn deep_dive_template(function: &str, template: &DeepDiveTemplate) -> String {
+    let mut missions = String::new();
+    for mission in &template.missions {
+        writeln!(
+            missions,
+            "FDeepDiveTemplateItem {{
+                mission: EMissionTemplate::{},
+                probability: {},
+                allowed_durations: {},
+                allowed_complexities: {},
+                can_only_appear_once: {},
+                can_only_appear_once_per_deep_dive_set: {},
+            }},",
+            mission.mission,
+            mission.probability,
+            slice("EMissionDuration", &mission.allowed_durations),
+            slice("EMissionComplexity", &mission.allowed_complexities),
+            mission.can_only_appear_once,
+            mission.can_only_appear_once_per_deep_dive_set,
+        )
+        .unwrap();
+    }
+    format!(
+        "pub fn {function}() -> &'static UDeepDiveTemplate {{
+            &UDeepDiveTemplate {{
+                mutator_count: {},
+                warning_count: {},
+                missions: &[{missions}],
+            }}
+        }}",
+        rand_interval(&template.mutator_count),
+        rand_interval(&template.warning_count),
+    )
+}
+
+/// Generates a codename list getter such as `names_first`.
+pub fn names(function: &str, names: &[String]) -> String {
+    let names = names.iter().map(|n| format!("{n:?},")).collect::<String>();
+    format!(
+        "pub fn {function}() -> &'static [&'static str] {{
+            &[{names}]
+        }}"
+    )
+}
+
+fn rand_interval(intervals: &[RandInterval]) -> String {
+    let items = intervals
+        .iter()
+        .map(|i| {
+            format!(
+                "FRandIntervalItem {{
+                    weight: {:?},
+                    range: FIRandRange {{ min: {}, max: {} }},
+                }},",
+                i.weight, i.min, i.max
+            )
+        })
+        .collect::<String>();
+    format!("FRandInterval {{ intervals: &[{items}] }}")
+}
+
+fn slice(enum_name: &str, variants: &[String]) -> String {
+    let variants = variants
+        .iter()
+        .map(|v| format!("{enum_name}::{v}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("&[{variants}]")
+}
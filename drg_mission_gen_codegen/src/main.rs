@@ -0,0 +1,433 @@
+//! Regenerates the data tables in `drg_mission_gen_core/src/data.rs` from the game assets saved
+//! to JSON by FModel or CUE4Parse, and reports enum variants that were added or removed by a game
+//! update.
+//!
+//! A single JSON file in the data pack layout of `drg_mission_gen_core` is read as well. Enums
+//! keep their hand-written annotations and are not rewritten; new variants are reported and have
+//! to be added by hand.
+
+mod assets;
+mod export;
+mod generate;
+mod regions;
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context};
+use clap::Parser;
+
+use assets::AssetNames;
+use export::Export;
+use regions::VariantChanges;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Directory of game assets saved to JSON by FModel or CUE4Parse, or a JSON file in the data
+    /// pack layout.
+    pub export: PathBuf,
+    /// Name of the `DeepDiveTemplate` export of normal Deep Dives.
+    #[arg(long)]
+    pub normal_template: Option<String>,
+    /// Name of the `DeepDiveTemplate` export of elite Deep Dives.
+    #[arg(long)]
+    pub hard_template: Option<String>,
+    /// Name of the `StringTable` export of the first codename words.
+    #[arg(long)]
+    pub names_first: Option<String>,
+    /// Name of the `StringTable` export of the last codename words.
+    #[arg(long)]
+    pub names_last: Option<String>,
+    /// Data pack layout JSON the DNA objective requirements are kept from, as the assets do not
+    /// hold them.
+    #[arg(long)]
+    pub base: Option<PathBuf>,
+    /// The `data.rs` to regenerate.
+    #[arg(long, default_value = "drg_mission_gen_core/src/data.rs")]
+    pub data_rs: PathBuf,
+    /// Only check that `data.rs` matches the export, fail if it does not.
+    #[arg(long)]
+    pub check: bool,
+}
+
+pub fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let export = if args.export.is_dir() {
+        let assets = assets::read_dir(&args.export)?;
+        let base = args.base.as_deref().map(read_export).transpose()?;
+        assets::to_export(&assets, &asset_names(&args)?, base.as_ref())?
+    } else {
+        read_export(&args.export)?
+    };
+    let source = std::fs::read_to_string(&args.data_rs)
+        .with_context(|| format!("reading {}", args.data_rs.display()))?;
+
+    let (regenerated, changes) = regenerate(&source, &export)?;
+    for change in &changes {
+        if !change.added.is_empty() {
+            println!("{} gained {}", change.enum_name, change.added.join(", "));
+        }
+        if !change.removed.is_empty() {
+            println!("{} lost {}", change.enum_name, change.removed.join(", "));
+        }
+    }
+
+    if regenerated == source {
+        println!("{} is up to date", args.data_rs.display());
+    } else if args.check {
+        bail!("{} is out of date", args.data_rs.display());
+    } else {
+        std::fs::write(&args.data_rs, regenerated)
+            .with_context(|| format!("writing {}", args.data_rs.display()))?;
+        println!("updated {}", args.data_rs.display());
+    }
+    Ok(())
+}
+
+fn read_export(path: &Path) -> anyhow::Result<Export> {
+    let export =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&export).context("parsing asset export")
+}
+
+fn asset_names(args: &Args) -> anyhow::Result<AssetNames> {
+    let name = |name: &Option<String>, flag: &str| {
+        name.clone()
+            .with_context(|| format!("{flag} is needed to read a directory of assets"))
+    };
+    Ok(AssetNames {
+        normal_template: name(&args.normal_template, "--normal-template")?,
+        hard_template: name(&args.hard_template, "--hard-template")?,
+        names_first: name(&args.names_first, "--names-first")?,
+        names_last: name(&args.names_last, "--names-last")?,
+    })
+}
+
+/// Returns the regenerated `data.rs` and the enums whose variants differ from the export.
+fn regenerate(source: &str, export: &Export) -> anyhow::Result<(String, Vec<VariantChanges>)> {
+    let templates = regions::enum_variants(source, "EMissionTemplate")?;
+    let dna = regions::enum_variants(source, "EMissionDNA")?;
+
+    let mut regenerated = source.to_string();
+    for (region, code) in [
+        (
+            "mission_templates",
+            generate::mission_templates(
+                export,
+                &regions::declaration_order(&templates, export.mission_templates.keys()),
+            ),
+        ),
+        (
+            "mission_dna",
+            generate::mission_dna(export, &regions::declaration_order(&dna, export.dna.keys())),
+        ),
+        (
+            "normal_template",
+            generate::deep_dive_template("get_normal_template", &export.normal_template),
+        ),
+        (
+            "hard_template",
+            generate::deep_dive_template("get_hard_template", &export.hard_template),
+        ),
+        (
+            "names_first",
+            generate::names("names_first", &export.names_first),
+        ),
+        (
+            "names_last",
+            generate::names("names_last", &export.names_last),
+        ),
+    ] {
+        regenerated = regions::replace(&regenerated, region, &rustfmt(&code)?)?;
+    }
+
+    let deep_dive_missions = || {
+        [&export.normal_template, &export.hard_template]
+            .into_iter()
+            .flat_map(|t| &t.missions)
+    };
+    let objectives = export.mission_templates.values().flat_map(|t| {
+        std::iter::once(&t.primary_objective)
+            .chain(&t.secondary_objectives)
+            .chain(&t.deep_dive_objectives)
+    });
+    let durations = export
+        .dna
+        .values()
+        .map(|d| &d.duration)
+        .chain(deep_dive_missions().flat_map(|m| &m.allowed_durations));
+    let complexities = export
+        .dna
+        .values()
+        .map(|d| &d.complexity)
+        .chain(deep_dive_missions().flat_map(|m| &m.allowed_complexities));
+
+    let changes = vec![
+        VariantChanges::new(
+            "EMissionTemplate",
+            &templates,
+            export
+                .mission_templates
+                .keys()
+                .chain(deep_dive_missions().map(|m| &m.mission)),
+            true,
+        ),
+        VariantChanges::new(
+            "EMissionDNA",
+            &dna,
+            export
+                .dna
+                .keys()
+                .chain(export.mission_templates.values().flat_map(|t| &t.dna)),
+            true,
+        ),
+        VariantChanges::new(
+            "EBiome",
+            &regions::enum_variants(source, "EBiome")?,
+            &export.biomes,
+            true,
+        ),
+        VariantChanges::new(
+            "EObjective",
+            &regions::enum_variants(source, "EObjective")?,
+            objectives,
+            false,
+        ),
+        VariantChanges::new(
+            "EMissionDuration",
+            &regions::enum_variants(source, "EMissionDuration")?,
+            durations,
+            false,
+        ),
+        VariantChanges::new(
+            "EMissionComplexity",
+            &regions::enum_variants(source, "EMissionComplexity")?,
+            complexities,
+            false,
+        ),
+        VariantChanges::new(
+            "EMissionMutator",
+            &regions::enum_variants(source, "EMissionMutator")?,
+            &export.deep_dive_mutators,
+            false,
+        ),
+        VariantChanges::new(
+            "EMissionWarning",
+            &regions::enum_variants(source, "EMissionWarning")?,
+            &export.deep_dive_warnings,
+            false,
+        ),
+    ];
+    let changes = changes.into_iter().filter(|c| !c.is_empty()).collect();
+
+    Ok((regenerated, changes))
+}
+
+fn rustfmt(code: &str) -> anyhow::Result<String> {
+    let mut child = Command::new(std::env::var_os("RUSTFMT").unwrap_or("rustfmt".into()))
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("running rustfmt")?;
+    child.stdin.take().unwrap().write_all(code.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("rustfmt failed on generated code:\n{code}");
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use drg_mission_gen_core::{DataPack, GameVersion};
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value};
+
+    const DATA_RS: &str = include_str!("../../drg_mission_gen_core/src/data.rs");
+
+    fn export() -> Export {
+        let pack = DataPack::from_version(GameVersion::LATEST);
+        serde_json::from_str(&serde_json::to_string(&pack).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_up_to_date() {
+        let (regenerated, changes) = regenerate(DATA_RS, &export()).unwrap();
+        assert_eq!(DATA_RS, regenerated);
+        assert!(changes.is_empty(), "{changes:?}");
+    }
+
+    /// The export as FModel would save the game assets, with references in each of the shapes
+    /// CUE4Parse writes them in.
+    fn assets(export: &Export) -> Vec<Value> {
+        let class = |name: &String| {
+            json!({
+                "ObjectName": format!("BlueprintGeneratedClass'{name}_C'"),
+                "ObjectPath": format!("FSD/Content/GameElements/{name}.1"),
+            })
+        };
+        let asset = |name: &String| {
+            json!({
+                "AssetPathName": format!("/Game/GameElements/{name}.{name}"),
+                "SubPathString": "",
+            })
+        };
+        let paths = |names: &[String]| {
+            let paths: Vec<_> = names
+                .iter()
+                .map(|n| format!("/Game/GameElements/{n}.{n}"))
+                .collect();
+            json!(paths)
+        };
+        let classes = |names: &[String]| json!(names.iter().map(class).collect::<Vec<_>>());
+        let assets = |names: &[String]| json!(names.iter().map(asset).collect::<Vec<_>>());
+        let interval = |intervals: &[export::RandInterval]| {
+            let intervals: Vec<_> = intervals
+                .iter()
+                .map(|i| json!({ "Weight": i.weight, "Range": { "Min": i.min, "Max": i.max } }))
+                .collect();
+            json!({ "Intervals": intervals })
+        };
+        let template = |name: &str, template: &export::DeepDiveTemplate| {
+            let missions: Vec<_> = template
+                .missions
+                .iter()
+                .map(|m| {
+                    json!({
+                        "Mission": asset(&m.mission),
+                        "Probability": m.probability,
+                        "AllowedDurations": paths(&m.allowed_durations),
+                        "AllowedComplexities": paths(&m.allowed_complexities),
+                        "bCanOnlyAppearOnce": m.can_only_appear_once,
+                        "bCanOnlyAppearOncePerDeepDiveSet":
+                            m.can_only_appear_once_per_deep_dive_set,
+                    })
+                })
+                .collect();
+            json!({
+                "Type": "DeepDiveTemplate",
+                "Name": name,
+                "Properties": {
+                    "Missions": missions,
+                    "MutatorCount": interval(&template.mutator_count),
+                    "WarningCount": interval(&template.warning_count),
+                },
+            })
+        };
+        let string_table = |name: &str, entries: &[String]| {
+            let entries: serde_json::Map<_, _> = entries
+                .iter()
+                .enumerate()
+                .map(|(i, e)| (format!("{name}_{i:03}"), json!(e)))
+                .collect();
+            json!({
+                "Type": "StringTable",
+                "Name": name,
+                "StringTable": { "TableNamespace": name, "KeysToEntries": entries },
+            })
+        };
+
+        let mut exports = vec![
+            json!({
+                "Type": "MissionSetup",
+                "Name": "MissionSetup",
+                "Properties": {
+                    "MissionTemplates": export
+                        .mission_templates
+                        .iter()
+                        .map(|(n, t)| json!({ "MissionTemplate": asset(n), "Rarity": t.rarity }))
+                        .collect::<Vec<_>>(),
+                },
+            }),
+            json!({
+                "Type": "DeepDiveSettings",
+                "Name": "DeepDiveSettings",
+                "Properties": {
+                    "Biomes": assets(&export.biomes),
+                    "Mutators": assets(&export.deep_dive_mutators),
+                    "Warnings": assets(&export.deep_dive_warnings),
+                },
+            }),
+            template("DDT_Normal", &export.normal_template),
+            template("DDT_Hard", &export.hard_template),
+            string_table("ST_NamesFirst", &export.names_first),
+            string_table("ST_NamesLast", &export.names_last),
+        ];
+        for (name, t) in &export.mission_templates {
+            exports.push(json!({
+                "Type": "MissionTemplate",
+                "Name": name,
+                "Properties": {
+                    "PrimaryObjective": class(&t.primary_objective),
+                    "SecondaryObjectives": classes(&t.secondary_objectives),
+                    "DeepDiveObjectives": classes(&t.deep_dive_objectives),
+                    "DNA": assets(&t.dna),
+                },
+            }));
+        }
+        for (name, dna) in &export.dna {
+            exports.push(json!({
+                "Type": "MissionDNA",
+                "Name": name,
+                "Properties": {
+                    "Duration": format!("EMissionDuration::{}", dna.duration),
+                    "Complexity": asset(&dna.complexity),
+                    "Weight": dna.weight,
+                },
+            }));
+        }
+        exports
+    }
+
+    #[test]
+    fn test_assets_up_to_date() {
+        let base = export();
+        let assets = serde_json::to_string(&assets(&base)).unwrap();
+        let assets: Vec<assets::AssetExport> = serde_json::from_str(&assets).unwrap();
+        let names = AssetNames {
+            normal_template: "DDT_Normal".to_string(),
+            hard_template: "DDT_Hard".to_string(),
+            names_first: "ST_NamesFirst".to_string(),
+            names_last: "ST_NamesLast".to_string(),
+        };
+
+        let export = assets::to_export(&assets, &names, Some(&base)).unwrap();
+        let (regenerated, changes) = regenerate(DATA_RS, &export).unwrap();
+        assert_eq!(DATA_RS, regenerated);
+        assert!(changes.is_empty(), "{changes:?}");
+
+        let export = assets::to_export(&assets, &names, None).unwrap();
+        assert!(export.dna.values().all(|d| d.requirement.is_none()));
+        assert!(assets::to_export(&assets[1..], &names, None).is_err());
+    }
+
+    #[test]
+    fn test_variant_changes() {
+        let mut export = export();
+        let dna = export.dna.remove("DNA_2_01").unwrap();
+        export.dna.insert("DNA_2_06".to_string(), dna);
+        for template in export.mission_templates.values_mut() {
+            for dna in &mut template.dna {
+                if dna == "DNA_2_01" {
+                    *dna = "DNA_2_06".to_string();
+                }
+            }
+        }
+
+        let (regenerated, changes) = regenerate(DATA_RS, &export).unwrap();
+        assert!(regenerated.contains("EMissionDNA::DNA_2_06 => &UMissionDNA {"));
+        assert_eq!(
+            changes,
+            vec![VariantChanges {
+                enum_name: "EMissionDNA",
+                added: vec!["DNA_2_06".to_string()],
+                removed: vec!["DNA_2_01".to_string()],
+            }]
+        );
+    }
+}
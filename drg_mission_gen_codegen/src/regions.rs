@@ -0,0 +1,138 @@
+//! Reading and replacing the parts of `data.rs` the generator owns.
+
+use anyhow::{bail, Context};
+
+/// Replaces the code between the `// codegen: begin <region>` and `// codegen: end <region>`
+/// markers.
+pub fn replace(source: &str, region: &str, code: &str) -> anyhow::Result<String> {
+    let begin = format!("// codegen: begin {region}\n");
+    let end = format!("// codegen: end {region}\n");
+    let start = source
+        .find(&begin)
+        .with_context(|| format!("missing marker {begin:?}"))?
+        + begin.len();
+    let stop = start
+        + source[start..]
+            .find(&end)
+            .with_context(|| format!("missing marker {end:?}"))?;
+    let code = keep_comments(&source[start..stop], code);
+    Ok(format!("{}{code}{}", &source[..start], &source[stop..]))
+}
+
+/// Lines of code before a commented line that have to match for the comment to be kept.
+const COMMENT_CONTEXT: usize = 4;
+
+/// Carries hand-written trailing comments over from `old` to the lines of `new` that have the same
+/// code and the same lines before them.
+fn keep_comments(old: &str, new: &str) -> String {
+    fn code(line: &str) -> &str {
+        line.split("//").next().unwrap().trim_end()
+    }
+    fn key<'a>(lines: &[&'a str], i: usize) -> Vec<&'a str> {
+        lines[i.saturating_sub(COMMENT_CONTEXT)..=i]
+            .iter()
+            .map(|l| code(l))
+            .collect()
+    }
+
+    let old: Vec<_> = old.lines().collect();
+    let commented: Vec<_> = (0..old.len())
+        .filter(|i| code(old[*i]) != old[*i].trim_end() && !code(old[*i]).is_empty())
+        .map(|i| (key(&old, i), old[i]))
+        .collect();
+
+    let new: Vec<_> = new.lines().collect();
+    let mut out = String::new();
+    for i in 0..new.len() {
+        let key = key(&new, i);
+        let line = commented
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map_or(new[i], |(_, line)| *line);
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Variants of `pub enum <name>` in declaration order.
+pub fn enum_variants(source: &str, name: &str) -> anyhow::Result<Vec<String>> {
+    let header = format!("pub enum {name} {{");
+    let mut lines = source.lines().skip_while(|l| !l.starts_with(&header));
+    if lines.next().is_none() {
+        bail!("enum {name} not found");
+    }
+    let mut variants = vec![];
+    for line in lines {
+        let line = line.split("//").next().unwrap().trim();
+        if line == "}" {
+            return Ok(variants);
+        }
+        let line = line.trim_end_matches(',');
+        if !line.is_empty() {
+            variants.push(line.to_string());
+        }
+    }
+    bail!("enum {name} is not closed")
+}
+
+/// How the variants referenced by an export differ from an enum in `data.rs`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct VariantChanges {
+    pub enum_name: &'static str,
+    pub added: Vec<String>,
+    /// Only filled for enums the export lists completely.
+    pub removed: Vec<String>,
+}
+
+impl VariantChanges {
+    pub fn new<'a>(
+        enum_name: &'static str,
+        existing: &[String],
+        exported: impl IntoIterator<Item = &'a String>,
+        complete: bool,
+    ) -> Self {
+        let mut exported: Vec<_> = exported.into_iter().cloned().collect();
+        exported.sort();
+        exported.dedup();
+
+        let added = exported
+            .iter()
+            .filter(|v| !existing.contains(v))
+            .cloned()
+            .collect();
+        let removed = if complete {
+            existing
+                .iter()
+                .filter(|v| !exported.contains(v))
+                .cloned()
+                .collect()
+        } else {
+            vec![]
+        };
+        VariantChanges {
+            enum_name,
+            added,
+            removed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Orders `names` by their position in `existing`, new names go last in alphabetical order.
+pub fn declaration_order<'a>(
+    existing: &[String],
+    names: impl IntoIterator<Item = &'a String>,
+) -> Vec<String> {
+    let mut names: Vec<_> = names.into_iter().cloned().collect();
+    names.sort_by_key(|n| {
+        (
+            existing.iter().position(|e| e == n).unwrap_or(usize::MAX),
+            n.clone(),
+        )
+    });
+    names
+}
@@ -1,11 +1,19 @@
 #![allow(non_camel_case_types)]
 #![allow(clippy::enum_variant_names)]
 
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
 use serde::{Deserialize, Serialize};
 use strum::{IntoStaticStr, VariantArray};
+use time::macros::date;
+use time::Date;
 
     // Annotations for terms used in search_seeds will be listed under their respective "pub enum" sections only
 
+// Regions between "codegen: begin/end" markers are regenerated from asset exports by
+// drg_mission_gen_codegen, edit the export instead of the code inside them.
+
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, VariantArray, Serialize, Deserialize,
 )]
@@ -216,6 +224,7 @@ pub enum EMissionTemplate {
     MissionType_Excavation,                                    // Heavy Extraction
 }
 
+// codegen: begin mission_templates
 impl EMissionTemplate {
     pub fn get(&self) -> &'static FMissionTemplateItem {
         match self {
@@ -519,6 +528,7 @@ impl EMissionTemplate {
         }
     }
 }
+// codegen: end mission_templates
 
 #[derive(Debug)]
 pub struct UMissionComplexity {}
@@ -584,8 +594,34 @@ pub struct UMissionDNA {
     pub duration: EMissionDuration,
     pub complexity: EMissionComplexity,
     pub weight: f32,
+    /// What the primary objective asks for with this DNA, `None` for objectives without an amount
+    /// like Escort Duty. Also `None` for DNA no mission template uses, like `DNA_Web_Large`, since
+    /// the game never shows their amount.
+    pub requirement: Option<ObjectiveRequirement>,
+}
+
+/// An amount of something an objective asks for, e.g. 400 Morkite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ObjectiveRequirement {
+    pub resource: Resource,
+    pub amount: u32,
 }
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, VariantArray, Serialize, Deserialize,
+)]
+pub enum Resource {
+    Morkite,
+    AlienEgg,
+    Aquarq,
+    MiniMule,
+    ResonanceCrystal,
+    DeepScan,
+    ResiniteMass,
+    Dreadnought,
+}
+
+// codegen: begin mission_dna
 impl EMissionDNA {
     pub fn get(self) -> &'static UMissionDNA {
         match self {
@@ -593,160 +629,252 @@ impl EMissionDNA {
                 duration: EMissionDuration::MD_Duration_Short,
                 complexity: EMissionComplexity::MD_Complexity_Simple,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Morkite,
+                    amount: 200,
+                }),
             },
             EMissionDNA::DNA_2_02 => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.5,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Morkite,
+                    amount: 250,
+                }),
             },
             EMissionDNA::DNA_2_03 => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Simple,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Morkite,
+                    amount: 225,
+                }),
             },
             EMissionDNA::DNA_2_04 => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.5,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Morkite,
+                    amount: 325,
+                }),
             },
             EMissionDNA::DNA_2_05 => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Morkite,
+                    amount: 400,
+                }),
             },
             EMissionDNA::DNA_Escort_LongAverage => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Escort_LongComplex => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Escort_MediumAverage => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Escort_MediumComplex => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Facility_Average => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Facility_DNA => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Facility_Simple => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Simple,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_FracturedSimple => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Short,
                 complexity: EMissionComplexity::MD_Complexity_Simple,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::AlienEgg,
+                    amount: 4,
+                }),
             },
             EMissionDNA::DNA_Fractured_Complex => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.5,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::AlienEgg,
+                    amount: 8,
+                }),
             },
             EMissionDNA::DNA_Fractured_Medium => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.5,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::AlienEgg,
+                    amount: 6,
+                }),
             },
             EMissionDNA::DNA_Motherlode_Long => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.5,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Aquarq,
+                    amount: 10,
+                }),
             },
             EMissionDNA::DNA_Motherlode_Short => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Aquarq,
+                    amount: 7,
+                }),
             },
             EMissionDNA::DNA_Refinery_Complex => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Refinery_Medium => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_SalvageFractured_Complex => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.5,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::MiniMule,
+                    amount: 3,
+                }),
             },
             EMissionDNA::DNA_SalvageFractured_Medium => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::MiniMule,
+                    amount: 2,
+                }),
             },
             EMissionDNA::DNA_Star_Complex => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 2.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Dreadnought,
+                    amount: 3,
+                }),
             },
             EMissionDNA::DNA_Star_Medium => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::Dreadnought,
+                    amount: 2,
+                }),
             },
             EMissionDNA::DNA_Tutorial => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Short,
                 complexity: EMissionComplexity::MD_Complexity_Simple,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Web_Large => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: None,
             },
             EMissionDNA::DNA_Web_Medium => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::DeepScan,
+                    amount: 5,
+                }),
             },
             EMissionDNA::DNA_Web_Small => &UMissionDNA {
                 duration: EMissionDuration::MD_Duration_Short,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::DeepScan,
+                    amount: 3,
+                }),
             },
-            EMissionDNA::DNA_Wheel_Long => &UMissionDNA {
-                duration: EMissionDuration::MD_Duration_Long,
+            EMissionDNA::DNA_Wheel_Medium => &UMissionDNA {
+                duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::ResiniteMass,
+                    amount: 3,
+                }),
             },
-            EMissionDNA::DNA_Wheel_LongComplex => &UMissionDNA {
-                duration: EMissionDuration::MD_Duration_Long,
+            EMissionDNA::DNA_Wheel_MediumComplex => &UMissionDNA {
+                duration: EMissionDuration::MD_Duration_Normal,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::ResiniteMass,
+                    amount: 3,
+                }),
             },
-            EMissionDNA::DNA_Wheel_Medium => &UMissionDNA {
-                duration: EMissionDuration::MD_Duration_Normal,
+            EMissionDNA::DNA_Wheel_Long => &UMissionDNA {
+                duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Average,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::ResiniteMass,
+                    amount: 4,
+                }),
             },
-            EMissionDNA::DNA_Wheel_MediumComplex => &UMissionDNA {
-                duration: EMissionDuration::MD_Duration_Normal,
+            EMissionDNA::DNA_Wheel_LongComplex => &UMissionDNA {
+                duration: EMissionDuration::MD_Duration_Long,
                 complexity: EMissionComplexity::MD_Complexity_Complex,
                 weight: 1.0,
+                requirement: Some(ObjectiveRequirement {
+                    resource: Resource::ResiniteMass,
+                    amount: 4,
+                }),
             },
         }
     }
 }
+// codegen: end mission_dna
 
 #[derive(Debug, Clone, Copy)]
 pub struct FIRandRange {
@@ -765,6 +893,11 @@ impl FIRandRange {
 pub struct UMissionSetup {
     pub extra_biomes: FIRandRange,
     pub global_required_missions: &'static [FRequiredMissionItem],
+    pub missions_per_biome: FIRandRange,
+    pub mutator_chance: f32,
+    pub warning_chance: f32,
+    pub mutators: &'static [EMissionMutator],
+    pub warnings: &'static [EMissionWarning],
 }
 pub fn get_mission_setup() -> &'static UMissionSetup {
     &UMissionSetup {
@@ -783,6 +916,40 @@ pub fn get_mission_setup() -> &'static UMissionSetup {
                 can_have_mutators: true,
             },
         ],
+        // not taken from the game, see `crate::experimental`
+        missions_per_biome: FIRandRange { min: 2, max: 3 },
+        mutator_chance: 0.2,
+        warning_chance: 0.25,
+        mutators: &[
+            EMissionMutator::MMUT_ExplosiveEnemies,
+            EMissionMutator::MMUT_ExterminationContract,
+            EMissionMutator::MMUT_SecretSecondary,
+            EMissionMutator::MMUT_XXXP,
+            EMissionMutator::MMUT_GoldRush,
+            EMissionMutator::MMUT_OxygenRich,
+            EMissionMutator::MMUT_RichInMinerals,
+            EMissionMutator::MMUT_Weakspot,
+            EMissionMutator::MMUT_BloodSugar,
+            EMissionMutator::MMUT_LowGravity,
+        ],
+        warnings: &[
+            EMissionWarning::WRN_RegenerativeEnemies,
+            EMissionWarning::WRN_HeroEnemies,
+            EMissionWarning::WRN_MacteraCave,
+            EMissionWarning::WRN_RockInfestation,
+            EMissionWarning::WRN_BulletHell,
+            EMissionWarning::WRN_CaveLeechDen,
+            EMissionWarning::WRN_NoOxygen,
+            EMissionWarning::WRN_ExploderInfestation,
+            EMissionWarning::WRN_Ghost,
+            EMissionWarning::WRN_LethalEnemies,
+            EMissionWarning::WRN_NoShields,
+            EMissionWarning::WRN_InfestedEnemies,
+            EMissionWarning::WRN_Swarmagedon,
+            EMissionWarning::WRN_RivalIncursion,
+            EMissionWarning::WRN_PitJawColony,
+            EMissionWarning::WRN_ScrabNestingGrounds,
+        ],
     }
 }
 
@@ -822,6 +989,148 @@ pub fn get_deep_dive_settings() -> &'static UDeepDiveSettings {
     }
 }
 
+/// Game version whose data assets generation follows.
+///
+/// A patch that changes generation data gets a new variant with its own tables, so Deep Dives from
+/// before the patch can still be regenerated.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, VariantArray, Serialize, Deserialize,
+)]
+pub enum GameVersion {
+    V1,
+}
+
+impl GameVersion {
+    pub const LATEST: GameVersion = GameVersion::V1;
+
+    pub fn data(self) -> &'static GenerationData {
+        static V1: LazyLock<GenerationData> = LazyLock::new(|| GenerationData {
+            biomes: EBiome::VARIANTS,
+            mission_templates: EMissionTemplate::VARIANTS
+                .iter()
+                .map(|t| (*t, t.get()))
+                .collect(),
+            dna: EMissionDNA::VARIANTS.iter().map(|d| (*d, d.get())).collect(),
+            normal_template: get_normal_template(),
+            hard_template: get_hard_template(),
+            deep_dive_settings: get_deep_dive_settings(),
+            names_first: names_first(),
+            names_last: names_last(),
+            bans: BanRules {
+                objective_biomes: banned(EObjective::VARIANTS, EBiome::VARIANTS, |o, b| {
+                    o.is_banned_in_biome(b)
+                }),
+                mutator_objectives: banned(
+                    EMissionMutator::VARIANTS,
+                    EObjective::VARIANTS,
+                    EMissionMutator::is_banned_objective,
+                ),
+                warning_objectives: banned(
+                    EMissionWarning::VARIANTS,
+                    EObjective::VARIANTS,
+                    EMissionWarning::is_banned_objective,
+                ),
+                warning_mutators: banned(
+                    EMissionWarning::VARIANTS,
+                    EMissionMutator::VARIANTS,
+                    EMissionWarning::is_banned_mutator,
+                ),
+            },
+        });
+
+        match self {
+            GameVersion::V1 => &V1,
+        }
+    }
+}
+
+/// Data set of a single game version.
+#[derive(Debug)]
+pub struct GenerationData {
+    /// Biomes a Deep Dive pair draws from.
+    pub biomes: &'static [EBiome],
+    pub mission_templates: BTreeMap<EMissionTemplate, &'static FMissionTemplateItem>,
+    pub dna: BTreeMap<EMissionDNA, &'static UMissionDNA>,
+    pub normal_template: &'static UDeepDiveTemplate,
+    pub hard_template: &'static UDeepDiveTemplate,
+    pub deep_dive_settings: &'static UDeepDiveSettings,
+    pub names_first: &'static [&'static str],
+    pub names_last: &'static [&'static str],
+    pub bans: BanRules,
+}
+
+impl GenerationData {
+    pub fn mission_template(&self, template: EMissionTemplate) -> &'static FMissionTemplateItem {
+        self.mission_templates[&template]
+    }
+
+    pub fn dna(&self, dna: EMissionDNA) -> &'static UMissionDNA {
+        self.dna[&dna]
+    }
+}
+
+/// Combinations that may not be rolled together. Anything not listed is allowed.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanRules {
+    /// Secondary objectives and the biomes they cannot appear in.
+    #[serde(default)]
+    pub objective_biomes: BTreeMap<EObjective, Vec<EBiome>>,
+    #[serde(default)]
+    pub mutator_objectives: BTreeMap<EMissionMutator, Vec<EObjective>>,
+    #[serde(default)]
+    pub warning_objectives: BTreeMap<EMissionWarning, Vec<EObjective>>,
+    #[serde(default)]
+    pub warning_mutators: BTreeMap<EMissionWarning, Vec<EMissionMutator>>,
+}
+
+impl BanRules {
+    pub fn is_objective_banned_in_biome(&self, objective: EObjective, biome: EBiome) -> bool {
+        is_listed(&self.objective_biomes, objective, biome)
+    }
+    pub fn is_mutator_banned_with_objective(
+        &self,
+        mutator: EMissionMutator,
+        objective: EObjective,
+    ) -> bool {
+        is_listed(&self.mutator_objectives, mutator, objective)
+    }
+    pub fn is_warning_banned_with_objective(
+        &self,
+        warning: EMissionWarning,
+        objective: EObjective,
+    ) -> bool {
+        is_listed(&self.warning_objectives, warning, objective)
+    }
+    pub fn is_warning_banned_with_mutator(
+        &self,
+        warning: EMissionWarning,
+        mutator: EMissionMutator,
+    ) -> bool {
+        is_listed(&self.warning_mutators, warning, mutator)
+    }
+}
+
+fn is_listed<K: Ord, V: PartialEq>(table: &BTreeMap<K, Vec<V>>, key: K, value: V) -> bool {
+    table.get(&key).is_some_and(|values| values.contains(&value))
+}
+
+fn banned<K: Ord + Copy, V: Copy>(
+    keys: &[K],
+    values: &[V],
+    is_banned: impl Fn(K, V) -> bool,
+) -> BTreeMap<K, Vec<V>> {
+    keys.iter()
+        .filter_map(|k| {
+            let banned: Vec<_> = values
+                .iter()
+                .copied()
+                .filter(|v| is_banned(*k, *v))
+                .collect();
+            (!banned.is_empty()).then_some((*k, banned))
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub struct UDeepDiveTemplate {
     pub missions: &'static [FDeepDiveTemplateItem],
@@ -849,6 +1158,7 @@ pub struct FDeepDiveTemplateItem {
     pub can_only_appear_once_per_deep_dive_set: bool,
 }
 
+// codegen: begin normal_template
 pub fn get_normal_template() -> &'static UDeepDiveTemplate {
     &UDeepDiveTemplate {
         mutator_count: FRandInterval {
@@ -965,7 +1275,9 @@ pub fn get_normal_template() -> &'static UDeepDiveTemplate {
         ],
     }
 }
+// codegen: end normal_template
 
+// codegen: begin hard_template
 pub fn get_hard_template() -> &'static UDeepDiveTemplate {
     &UDeepDiveTemplate {
         mutator_count: FRandInterval {
@@ -1076,6 +1388,7 @@ pub fn get_hard_template() -> &'static UDeepDiveTemplate {
         ],
     }
 }
+// codegen: end hard_template
 
 #[derive(
     Debug,
@@ -1190,6 +1503,19 @@ pub enum EMissionWarning {                     // Annotated for Deep Dive warnin
 }
 
 impl EObjective {
+    /// Fixed amount a Deep Dive secondary objective asks for. Primary objective amounts depend on
+    /// the DNA, see [`UMissionDNA::requirement`].
+    pub fn deep_dive_requirement(self) -> Option<ObjectiveRequirement> {
+        let (resource, amount) = match self {
+            EObjective::OBJ_DD_AlienEggs => (Resource::AlienEgg, 2),
+            EObjective::OBJ_DD_DeepScan => (Resource::ResonanceCrystal, 2),
+            EObjective::OBJ_DD_Morkite => (Resource::Morkite, 150),
+            EObjective::OBJ_DD_RepairMinimules => (Resource::MiniMule, 2),
+            _ => return None,
+        };
+        Some(ObjectiveRequirement { resource, amount })
+    }
+
     pub fn is_banned_in_biome(self, biome: EBiome) -> bool {
         match self {
             EObjective::OBJ_2nd_DestroyEggs => {
@@ -1307,41 +1633,74 @@ pub enum ESeason {
     Season3,
     Season4,
     Season5,
-    Season6,
 }
 
 impl ESeason {
-    pub fn from_index(index: usize) -> Self {
-        Self::VARIANTS[index]
+    pub fn from_index(index: i32) -> Result<Self, crate::UnknownSeason> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| seasons().get(i))
+            .map(|s| s.season)
+            .ok_or(crate::UnknownSeason(index))
     }
     pub fn get(&self) -> &'static USeason {
-        match self {
-            ESeason::Season0 => &USeason {
-                mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
-            },
-            ESeason::Season1 => &USeason {
-                mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
-            },
-            ESeason::Season2 => &USeason {
-                mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
-            },
-            ESeason::Season3 => &USeason {
-                mission_map_event_zone_type: ESeasonMissionMapOverlayType::Plague,
-            },
-            ESeason::Season4 => &USeason {
-                mission_map_event_zone_type: ESeasonMissionMapOverlayType::Plague,
-            },
-            ESeason::Season5 => &USeason {
-                mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
-            },
-            ESeason::Season6 => &USeason {
-            mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
-            },
-        }
+        &seasons()[*self as usize]
+    }
+    /// Latest season released on or before `date`. Dates before the game's release fall back to
+    /// [`ESeason::Season0`].
+    pub fn at(date: Date) -> Self {
+        seasons()
+            .iter()
+            .rev()
+            .find(|s| s.release <= date)
+            .map_or(ESeason::Season0, |s| s.season)
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Every released season, indexed by [`ESeason`]. Season 0 is the game without a season, from
+/// its 1.0 release on.
+pub fn seasons() -> &'static [USeason] {
+    &[
+        USeason {
+            season: ESeason::Season0,
+            name: "Season 0",
+            release: date!(2020 - 05 - 13),
+            mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
+        },
+        USeason {
+            season: ESeason::Season1,
+            name: "Rival Incursion",
+            release: date!(2021 - 11 - 03),
+            mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
+        },
+        USeason {
+            season: ESeason::Season2,
+            name: "Rival Escalation",
+            release: date!(2022 - 06 - 02),
+            mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
+        },
+        USeason {
+            season: ESeason::Season3,
+            name: "Plaguefall",
+            release: date!(2022 - 11 - 03),
+            mission_map_event_zone_type: ESeasonMissionMapOverlayType::Plague,
+        },
+        USeason {
+            season: ESeason::Season4,
+            name: "Critical Corruption",
+            release: date!(2023 - 05 - 04),
+            mission_map_event_zone_type: ESeasonMissionMapOverlayType::Plague,
+        },
+        USeason {
+            season: ESeason::Season5,
+            name: "Drilling Deeper",
+            release: date!(2023 - 11 - 02),
+            mission_map_event_zone_type: ESeasonMissionMapOverlayType::None,
+        },
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ESeasonMissionMapOverlayType {
     None,
     Plague,
@@ -1349,6 +1708,9 @@ pub enum ESeasonMissionMapOverlayType {
 
 #[derive(Debug)]
 pub struct USeason {
+    pub season: ESeason,
+    pub name: &'static str,
+    pub release: Date,
     pub mission_map_event_zone_type: ESeasonMissionMapOverlayType,
 }
 
@@ -1373,6 +1735,7 @@ pub struct UDeepDive {
     pub missions: Vec<UGeneratedMission>,
 }
 
+// codegen: begin names_first
 pub fn names_first() -> &'static [&'static str] {
     &[
         "Angry",
@@ -1598,7 +1961,9 @@ pub fn names_first() -> &'static [&'static str] {
         "Carnivorous",
     ]
 }
+// codegen: end names_first
 
+// codegen: begin names_last
 pub fn names_last() -> &'static [&'static str] {
     &[
         "Overhang",
@@ -1840,3 +2205,4 @@ pub fn names_last() -> &'static [&'static str] {
         "Tunnel",
     ]
 }
+// codegen: end names_last
@@ -0,0 +1,312 @@
+//! Generation of the 30 minute mission board.
+//!
+//! Experimental: the missions per biome, the mutator and warning chances and the mutator and
+//! warning pools of [`UMissionSetup`][crate::data::UMissionSetup] are placeholders, not values from the game's MissionSetup
+//! asset, so generated boards do not match the game yet. The module stays private to the crate
+//! until they do.
+
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+use thiserror::Error;
+
+use crate::data::get_mission_setup;
+use crate::explain::Trace;
+use crate::rand::FRandomStream;
+use crate::{
+    init_helpers, select_mutator, select_warning, shuffle, try_gen_mission, EBiome,
+    EMissionComplexity, EMissionDuration, EMissionTemplate, EPlanetZone, ESeason,
+    ESeasonMissionMapOverlayType, FGlobalMissionSeed, FPlanetZoneItem, GameVersion, GenStep,
+    UGeneratedMission, UnknownSeason,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MissionMap {
+    pub season: ESeason,
+    pub zones: Vec<MissionMapZone>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MissionMapZone {
+    pub zone: EPlanetZone,
+    pub biomes: Vec<EBiome>,
+    pub missions: Vec<UGeneratedMission>,
+    pub plague: bool,
+}
+
+/// A mission board that could not be generated.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MissionMapError {
+    #[error(transparent)]
+    UnknownSeason(#[from] UnknownSeason),
+    #[error("no mission template has a rarity to pick it by")]
+    MissionTemplate,
+    #[error("{0:?}: {1}")]
+    Mission(EMissionTemplate, GenStep),
+}
+
+/// Generates the mission board for the given global mission seed.
+///
+/// # Panics
+///
+/// For seasons with a plague overlay, seasons 3 and 4, whose regions are not implemented yet.
+pub fn gen_mission_map(seed: &FGlobalMissionSeed) -> Result<MissionMap, MissionMapError> {
+    let season = ESeason::from_index(seed.season)?;
+    let mut rand = FRandomStream::new(seed.random_seed as u32);
+    let setup = get_mission_setup();
+
+    let mut helpers = init_helpers(&mut rand);
+
+    let extra_biomes = {
+        let range = setup.extra_biomes;
+        let delta = range.max - range.min + 1;
+        if delta <= 0 {
+            0
+        } else {
+            range.rand(&mut rand)
+        }
+    };
+
+    rand.mutate(); // TODO unused?
+    let saved = rand.seed(); // surely there is some logical explanation here... forking rand stream?
+    let rand_helper = (rand.get_fraction() * helpers.len() as f32) as usize;
+    rand.set_seed(saved);
+
+    match season.get().mission_map_event_zone_type {
+        ESeasonMissionMapOverlayType::None => {
+            let pick = &mut helpers[rand_helper];
+
+            pick.picked_maybe = true;
+
+            add_extra_biomes(pick, extra_biomes);
+        }
+        ESeasonMissionMapOverlayType::Plague => {
+            todo!("plague regions")
+        }
+    }
+
+    // TODO verify when global_missions.len() > zones
+    let mut global_missions: Vec<_> = setup
+        .global_required_missions
+        .iter()
+        .map(Some)
+        .chain(std::iter::repeat(None))
+        .take(helpers.len())
+        .collect();
+
+    shuffle(&mut rand, &mut global_missions);
+
+    for (helper, global_mission) in helpers.iter_mut().zip(global_missions) {
+        // TODO verify order of required missions relative to the random fill
+        for required in helper
+            .zone
+            .get()
+            .required_missions
+            .iter()
+            .chain(global_mission)
+        {
+            let biome = *rand.rand_item(&helper.biomes);
+            let mission = gen_map_mission(
+                required.mission_template,
+                biome,
+                Some(required.complexity),
+                Some(required.duration),
+                required.can_have_mutators,
+                &mut rand,
+            )?;
+            helper.missions.push(mission);
+        }
+
+        for biome in helper.biomes.clone() {
+            let count = setup.missions_per_biome.rand(&mut rand);
+            for _ in 0..count {
+                let template = select_map_template(&mut rand)?;
+                let mission = gen_map_mission(template, biome, None, None, true, &mut rand)?;
+                helper.missions.push(mission);
+            }
+        }
+    }
+
+    Ok(MissionMap {
+        season,
+        zones: helpers
+            .into_iter()
+            .map(|helper| MissionMapZone {
+                zone: *helper.zone,
+                biomes: helper.biomes,
+                missions: helper.missions,
+                plague: helper.plague_maybe,
+            })
+            .collect(),
+    })
+}
+
+fn add_extra_biomes(pick: &mut FPlanetZoneItem, mut extra_biomes: i32) {
+    for biome in pick.zone.get().biomes {
+        if extra_biomes <= 0 {
+            break;
+        }
+
+        if pick.biomes.contains(biome) {
+            continue;
+        }
+
+        pick.biomes.push(*biome);
+
+        extra_biomes -= 1;
+    }
+}
+
+fn select_map_template(rand: &mut FRandomStream) -> Result<EMissionTemplate, MissionMapError> {
+    let total: f32 = EMissionTemplate::VARIANTS
+        .iter()
+        .map(|t| t.get().rarity)
+        .sum();
+
+    let mut sum = 0.0;
+    let select = rand.get_fraction() * total;
+
+    EMissionTemplate::VARIANTS
+        .iter()
+        .filter(|t| t.get().rarity > 0.0)
+        .find(|t| {
+            sum += t.get().rarity;
+            sum >= select
+        })
+        .copied()
+        .ok_or(MissionMapError::MissionTemplate)
+}
+
+fn gen_map_mission(
+    template: EMissionTemplate,
+    biome: EBiome,
+    complexity_limit: Option<EMissionComplexity>,
+    duration_limit: Option<EMissionDuration>,
+    can_have_mutators: bool,
+    rand: &mut FRandomStream,
+) -> Result<UGeneratedMission, MissionMapError> {
+    let setup = get_mission_setup();
+    let bans = &GameVersion::LATEST.data().bans;
+
+    rand.mutate();
+    let mission_seed = rand.seed();
+    let mut mission = try_gen_mission(
+        template,
+        biome,
+        mission_seed,
+        complexity_limit,
+        duration_limit,
+    )
+    .map_err(|step| MissionMapError::Mission(template, step))?;
+    let primary_objective = mission.primary_objective.objective();
+    let secondary_objectives: Vec<_> = mission
+        .secondary_objectives
+        .iter()
+        .map(|o| o.objective())
+        .collect();
+
+    let mut mutator = None;
+    let mut warning = None;
+    if can_have_mutators {
+        if rand.get_fraction() < setup.mutator_chance {
+            mutator = Some(
+                select_mutator(
+                    bans,
+                    setup.mutators,
+                    primary_objective,
+                    &secondary_objectives,
+                    rand,
+                    &mut Trace::off(),
+                )
+                .map_err(|step| MissionMapError::Mission(template, step))?,
+            );
+        }
+        if rand.get_fraction() < setup.warning_chance {
+            warning = Some(
+                select_warning(
+                    bans,
+                    setup.warnings,
+                    mutator,
+                    primary_objective,
+                    &secondary_objectives,
+                    rand,
+                    &mut Trace::off(),
+                )
+                .map_err(|step| MissionMapError::Mission(template, step))?,
+            );
+        }
+    }
+
+    mission.mutators = mutator.into_iter().collect();
+    mission.warnings = warning.into_iter().collect();
+    Ok(mission)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gen() {
+        let seed = FGlobalMissionSeed {
+            random_seed: 2,
+            season: 5,
+            map_key: 5,
+        };
+
+        let map = gen_mission_map(&seed).unwrap();
+        assert_eq!(
+            map,
+            gen_mission_map(&seed).unwrap(),
+            "generation is deterministic"
+        );
+        assert_eq!(map.zones.len(), EPlanetZone::VARIANTS.len());
+
+        for zone in &map.zones {
+            assert!(zone
+                .biomes
+                .iter()
+                .all(|b| zone.zone.get().biomes.contains(b)));
+            for required in zone.zone.get().required_missions {
+                assert!(zone
+                    .missions
+                    .iter()
+                    .any(|m| m.template == required.mission_template
+                        && m.dna.get().complexity == required.complexity
+                        && m.dna.get().duration == required.duration));
+            }
+            for mission in &zone.missions {
+                assert!(zone.biomes.contains(&mission.biome));
+                assert!(mission
+                    .template
+                    .get()
+                    .mission_template
+                    .dna
+                    .contains(&mission.dna));
+            }
+        }
+
+        let facilities = map
+            .zones
+            .iter()
+            .flat_map(|z| &z.missions)
+            .filter(|m| m.template == EMissionTemplate::MissionType_Facility)
+            .count();
+        assert_eq!(
+            facilities,
+            get_mission_setup().global_required_missions.len()
+        );
+    }
+
+    #[test]
+    fn test_unknown_season() {
+        let unknown = ESeason::VARIANTS.len() as i32;
+        assert_eq!(
+            gen_mission_map(&FGlobalMissionSeed {
+                random_seed: 0,
+                season: unknown,
+                map_key: 0,
+            }),
+            Err(MissionMapError::UnknownSeason(UnknownSeason(unknown)))
+        );
+    }
+}
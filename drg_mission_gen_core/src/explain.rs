@@ -0,0 +1,255 @@
+//! Instrumented Deep Dive generation that records every random decision: the candidates with their
+//! weights, the fraction drawn and the candidate it picked.
+//!
+//! Generation always runs with a [`Trace`], which only builds candidate lists when it is recording,
+//! so the regular and search paths pay nothing for it.
+
+use serde::Serialize;
+
+use crate::rand::FRandomStream;
+use crate::{
+    try_deep_dive_biomes, DeepDiveKind, DeepDiveStages, EBiome, EDreadnought, EMissionComplexity,
+    EMissionDNA, EMissionDuration, EMissionMutator, EMissionTemplate, EMissionWarning, EObjective,
+    GameVersion, GenError, GenerationData, UDeepDive,
+};
+
+/// A Deep Dive pair with the decisions that generated it, in the order they were drawn.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeepDiveExplanation {
+    pub seed: u32,
+    pub normal: UDeepDive,
+    pub elite: UDeepDive,
+    pub decisions: Vec<Decision>,
+}
+
+/// A single draw from the stream of a dive.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Decision {
+    /// Dive the draw belongs to. Biome draws use the pair's stream and are attributed to the dive
+    /// whose biome they pick.
+    pub kind: DeepDiveKind,
+    /// Stage 0 to 2, `None` for draws before the first stage.
+    pub stage: Option<usize>,
+    pub step: Step,
+    /// Candidates in the order the draw walks them. Candidates a filter removed are kept at the
+    /// end with a weight of 0.
+    pub candidates: Vec<Candidate>,
+    /// Fraction in `0..1` the draw is based on.
+    pub fraction: f32,
+    /// Index of the picked candidate.
+    pub picked: usize,
+}
+
+impl Decision {
+    pub fn picked(&self) -> &Choice {
+        &self.candidates[self.picked].value
+    }
+
+    /// Probability the picked candidate had, given the candidates.
+    pub fn probability(&self) -> f32 {
+        let total: f32 = self.candidates.iter().map(|c| c.weight).sum();
+        self.candidates[self.picked].weight / total
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Step {
+    Biome,
+    FirstName,
+    LastName,
+    /// Interval the number of stages with a mutator is drawn from.
+    MutatorInterval,
+    MutatorCount,
+    /// Stage removed from the stages that get a mutator.
+    MutatorStageRemoved,
+    WarningInterval,
+    WarningCount,
+    WarningStageRemoved,
+    Mission,
+    SecondaryObjective,
+    Mutator,
+    Warning,
+    Dna,
+    Dreadnought,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Candidate {
+    pub value: Choice,
+    /// Relative weight, 1 for uniform draws.
+    pub weight: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Choice {
+    Biome(EBiome),
+    Name(&'static str),
+    Interval {
+        min: i32,
+        max: i32,
+    },
+    Count(i32),
+    Stage(usize),
+    Mission {
+        template: EMissionTemplate,
+        duration: Option<EMissionDuration>,
+        complexity: Option<EMissionComplexity>,
+    },
+    Objective(EObjective),
+    Mutator(EMissionMutator),
+    Warning(EMissionWarning),
+    Dna(EMissionDNA),
+    Dreadnought(EDreadnought),
+}
+
+impl Candidate {
+    pub(crate) fn uniform(value: Choice) -> Self {
+        Candidate { value, weight: 1.0 }
+    }
+}
+
+/// Records decisions while generating, or does nothing when it is off.
+#[derive(Debug)]
+pub(crate) struct Trace {
+    decisions: Option<Vec<Decision>>,
+    pub(crate) kind: DeepDiveKind,
+    pub(crate) stage: Option<usize>,
+}
+
+impl Trace {
+    pub(crate) fn new(kind: DeepDiveKind, recording: bool) -> Self {
+        Trace {
+            decisions: recording.then(Vec::new),
+            kind,
+            stage: None,
+        }
+    }
+
+    pub(crate) fn off() -> Self {
+        Self::new(DeepDiveKind::Normal, false)
+    }
+
+    /// Records a draw that was just made from `rand`. `candidates` is only called when recording.
+    pub(crate) fn record(
+        &mut self,
+        step: Step,
+        rand: &FRandomStream,
+        picked: usize,
+        candidates: impl FnOnce() -> Vec<Candidate>,
+    ) {
+        if let Some(decisions) = &mut self.decisions {
+            decisions.push(Decision {
+                kind: self.kind,
+                stage: self.stage,
+                step,
+                candidates: candidates(),
+                fraction: rand.fraction(),
+                picked,
+            });
+        }
+    }
+
+    pub(crate) fn take(&mut self) -> Vec<Decision> {
+        self.decisions.take().unwrap_or_default()
+    }
+}
+
+/// Generates the Deep Dive pair for `seed` and records how every choice was made.
+pub fn explain_deep_dive_pair(seed: u32) -> Result<DeepDiveExplanation, GenError> {
+    explain_deep_dive_pair_with(GameVersion::LATEST.data(), seed)
+}
+
+/// [`explain_deep_dive_pair`] with arbitrary generation data, such as a loaded
+/// [`crate::DataPack`].
+pub fn explain_deep_dive_pair_with(
+    data: &GenerationData,
+    seed: u32,
+) -> Result<DeepDiveExplanation, GenError> {
+    let deep_dive_seed = seed & 0x1ffff;
+    let mut trace = Trace::new(DeepDiveKind::Normal, true);
+    let (normal_biome, elite_biome) = try_deep_dive_biomes(data, deep_dive_seed, &mut trace)?;
+    let mut decisions = trace.take();
+
+    let mut used_missions = vec![];
+    let mut dives = vec![];
+    for (kind, biome) in [
+        (DeepDiveKind::Normal, normal_biome),
+        (DeepDiveKind::Elite, elite_biome),
+    ] {
+        let mut stages = DeepDiveStages::new(data, kind, deep_dive_seed, biome, true)?;
+        for _ in 0..3 {
+            stages.next_stage(&mut used_missions)?;
+        }
+        decisions.extend(stages.trace.take());
+        dives.push(stages.finish());
+    }
+    let elite = dives.pop().unwrap();
+    let normal = dives.pop().unwrap();
+
+    Ok(DeepDiveExplanation {
+        seed,
+        normal,
+        elite,
+        decisions,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_deep_dive_pair;
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain_deep_dive_pair(216).unwrap();
+        assert_eq!(
+            (explanation.normal.clone(), explanation.elite.clone()),
+            gen_deep_dive_pair(216)
+        );
+
+        for kind in [DeepDiveKind::Normal, DeepDiveKind::Elite] {
+            let dive = match kind {
+                DeepDiveKind::Normal => &explanation.normal,
+                DeepDiveKind::Elite => &explanation.elite,
+            };
+            let decisions: Vec<_> = explanation
+                .decisions
+                .iter()
+                .filter(|d| d.kind == kind)
+                .collect();
+            let find = |stage, step| {
+                decisions
+                    .iter()
+                    .filter(move |d| d.stage == stage && d.step == step)
+                    .map(|d| d.picked())
+            };
+
+            assert_eq!(
+                find(None, Step::Biome).collect::<Vec<_>>(),
+                vec![&Choice::Biome(dive.biome)]
+            );
+            for (i, mission) in dive.missions.iter().enumerate() {
+                assert!(matches!(
+                    find(Some(i), Step::Mission).next(),
+                    Some(Choice::Mission { template, .. }) if *template == mission.template
+                ));
+                assert_eq!(
+                    find(Some(i), Step::Dna).next(),
+                    Some(&Choice::Dna(mission.dna))
+                );
+                let mutators: Vec<_> = find(Some(i), Step::Mutator).collect();
+                let expected: Vec<_> = mission
+                    .mutators
+                    .iter()
+                    .map(|m| Choice::Mutator(*m))
+                    .collect();
+                assert_eq!(mutators, expected.iter().collect::<Vec<_>>());
+            }
+        }
+
+        for decision in &explanation.decisions {
+            assert!((0.0..1.0).contains(&decision.fraction));
+            assert!(decision.probability() > 0.0, "{decision:?}");
+        }
+    }
+}
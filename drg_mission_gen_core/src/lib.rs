@@ -1,17 +1,64 @@
 mod data;
-mod rand;
-
-use data::{get_deep_dive_settings, get_hard_template, get_mission_setup, get_normal_template};
+// Not public until the MissionSetup values it uses are taken from the game.
+#[allow(dead_code)]
+mod experimental;
+mod explain;
+mod lookup;
+mod names;
+mod pack;
+pub mod rand;
+mod replay;
+mod search;
+mod stats;
+mod table;
+mod validate;
 
 // Public re-exports.
 pub use data::{
-    EBiome, EDreadnought, EMissionComplexity, EMissionDNA, EMissionDuration, EMissionMutator,
-    EMissionTemplate, EMissionWarning, EObjective, EPlanetZone, FDeepDiveTemplateItem,
-    FRandInterval, ObjectiveInstance, UDeepDive, UDeepDiveTemplate, UGeneratedMission, UMissionDNA,
+    BanRules, EBiome, EDreadnought, EMissionComplexity, EMissionDNA, EMissionDuration,
+    EMissionMutator, EMissionTemplate, EMissionWarning, EObjective, EPlanetZone, ESeason,
+    ESeasonMissionMapOverlayType, FDeepDiveTemplateItem, FIRandRange, FMissionTemplateItem,
+    FRandInterval, FRequiredMissionItem, GameVersion, GenerationData,
+    ObjectiveInstance, ObjectiveRequirement, Resource, UDeepDive, UDeepDiveSettings,
+    UDeepDiveTemplate, UGeneratedMission, UMissionComplexity, UMissionDNA, UMissionDuration,
+    UMissionTemplate, UPlanetZone, USeason,
 };
 
+pub use explain::{
+    explain_deep_dive_pair, explain_deep_dive_pair_with, Candidate, Choice, Decision,
+    DeepDiveExplanation, Step,
+};
+pub use lookup::{
+    find_deep_dive_seeds, find_deep_dive_seeds_by_mission_seed,
+    find_deep_dive_seeds_by_mission_seed_for, DeepDiveKind, DeepDiveMatch, DeepDiveObservation,
+    LookupError, MissionSeedMatch, DEEP_DIVE_SEED_COUNT,
+};
+pub use names::Language;
+pub use pack::{
+    DataPack, DataPackError, DeepDiveMission, DeepDiveTemplate, Dna, MissionTemplate, RandInterval,
+};
+pub use replay::{
+    first_divergence, parse_game_trace, record_deep_dive_pair_draws,
+    record_deep_dive_pair_draws_with, Divergence, TraceParseError, TracedSeed,
+};
+pub use search::{
+    search_deep_dive_seeds, search_deep_dive_seeds_with, try_search_deep_dive_seeds_with,
+    LazyDeepDivePair,
+};
+pub use stats::{
+    DeepDiveStats, Distribution, Feature, FeatureValue, JointDistribution, Probability, Scope,
+};
+pub use table::{DeepDiveTable, Postings, TableDive, TableError, TableStage, PAIR_SIZE};
+pub use validate::{
+    deep_dive_violations, try_validate_deep_dive_with, validate_deep_dive, validate_deep_dive_with,
+    DeepDiveValidation, Rule, Violation,
+};
+
+use explain::Trace;
 use rand::FRandomStream;
 use strum::VariantArray;
+use thiserror::Error;
+use time::{Duration, OffsetDateTime, UtcOffset};
 
 fn sample_zones(rand: &mut FRandomStream, zone: EPlanetZone) -> data::EBiome {
     let total: f32 = zone
@@ -35,20 +82,45 @@ fn sample_zones(rand: &mut FRandomStream, zone: EPlanetZone) -> data::EBiome {
         .unwrap()
 }
 
-fn sample_rand_interval(rand: &mut FRandomStream, interval: &FRandInterval) -> i32 {
+fn sample_rand_interval(
+    rand: &mut FRandomStream,
+    interval: &FRandInterval,
+    trace: &mut Trace,
+    steps: (Step, Step),
+) -> i32 {
     let total: f32 = interval.intervals.iter().map(|b| b.weight).sum();
 
     let mut sum = 0.0;
     let select = rand.get_fraction() * total;
 
-    interval
-        .intervals
-        .iter()
-        .find_map(|i| {
-            sum += i.weight;
-            (sum >= select).then(|| rand.rand_range(i.range.min, i.range.max))
-        })
-        .unwrap_or_default()
+    let Some(picked) = interval.intervals.iter().position(|i| {
+        sum += i.weight;
+        sum >= select
+    }) else {
+        return 0;
+    };
+    trace.record(steps.0, rand, picked, || {
+        interval
+            .intervals
+            .iter()
+            .map(|i| Candidate {
+                value: Choice::Interval {
+                    min: i.range.min,
+                    max: i.range.max,
+                },
+                weight: i.weight,
+            })
+            .collect()
+    });
+
+    let range = interval.intervals[picked].range;
+    let count = rand.rand_range(range.min, range.max);
+    trace.record(steps.1, rand, (count - range.min) as usize, || {
+        (range.min..=range.max)
+            .map(|c| Candidate::uniform(Choice::Count(c)))
+            .collect()
+    });
+    count
 }
 
 #[derive(Debug)]
@@ -76,34 +148,45 @@ fn init_helpers(rand: &mut FRandomStream) -> Vec<FPlanetZoneItem> {
 
 fn shuffle<T>(rand: &mut FRandomStream, vec: &mut [T]) {
     for i in 0..vec.len() {
-        //println!("SEED = {:X}", rand.0);
         let swap_index = rand.rand_helper(vec.len() as i32) as usize;
-        println!("swap {} {}", i, swap_index);
         vec.swap(i, swap_index);
     }
 }
 
-fn randomly_shrink<T>(rand: &mut FRandomStream, size: usize, vec: &mut Vec<T>) {
+fn randomly_shrink(
+    rand: &mut FRandomStream,
+    size: usize,
+    vec: &mut Vec<usize>,
+    trace: &mut Trace,
+    step: Step,
+) {
     while vec.len() > size {
-        vec.swap_remove(rand.rand_helper(vec.len() as i32) as usize);
+        let i = rand.rand_helper(vec.len() as i32) as usize;
+        trace.record(step, rand, i, || {
+            vec.iter()
+                .map(|s| Candidate::uniform(Choice::Stage(*s)))
+                .collect()
+        });
+        vec.swap_remove(i);
     }
 }
 
 impl UGeneratedMission {
-    fn initialize_objectives(&mut self) {
+    fn initialize_objectives(&mut self, trace: &mut Trace) -> Result<(), GenStep> {
         let mut rand = FRandomStream::new(self.seed);
         rand.mutate(); // UGeneratedMission::InitializePLS
         rand.mutate(); // AProceduralSetup::InitializePLS
 
-        self.primary_objective.init(&mut rand);
+        self.primary_objective.init(&mut rand, trace)?;
         for obj in &mut self.secondary_objectives {
-            obj.init(&mut rand);
+            obj.init(&mut rand, trace)?;
         }
+        Ok(())
     }
 }
 
 impl ObjectiveInstance {
-    fn init(&mut self, rand: &mut FRandomStream) {
+    fn init(&mut self, rand: &mut FRandomStream, trace: &mut Trace) -> Result<(), GenStep> {
         if let ObjectiveInstance::Elimination {
             kind,
             ref mut targets,
@@ -112,7 +195,7 @@ impl ObjectiveInstance {
             let count = match kind {
                 EObjective::OBJ_Eliminate_Eggs => 2,
                 EObjective::OBJ_DD_Elimination_Eggs => 1,
-                _ => unreachable!(),
+                _ => return Err(GenStep::EliminationTargets(*kind)),
             };
             let mut pool = vec![
                 data::EDreadnought::Dreadnought,
@@ -120,83 +203,270 @@ impl ObjectiveInstance {
                 data::EDreadnought::Twins,
             ];
             *targets = (0..count)
-                .map(|_| rand.rand_swap_remove(&mut pool))
+                .map(|_| {
+                    let i = rand.rand_helper(pool.len() as i32) as usize;
+                    trace.record(Step::Dreadnought, rand, i, || {
+                        pool.iter()
+                            .map(|d| Candidate::uniform(Choice::Dreadnought(*d)))
+                            .collect()
+                    });
+                    pool.swap_remove(i)
+                })
                 .collect();
         }
+        Ok(())
     }
 }
 
-#[derive(Debug)]
-struct FGlobalMissionSeed {
-    random_seed: i32,
-    season: i32,
-    map_key: i32,
+/// A Deep Dive that could not be generated, e.g. because a data pack leaves a pool empty.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{kind:?} Deep Dive{}: {step}", stage.map(|s| format!(" stage {}", s + 1)).unwrap_or_default())]
+pub struct GenError {
+    pub kind: DeepDiveKind,
+    /// Stage 0 to 2, `None` for steps before the first stage.
+    pub stage: Option<usize>,
+    pub step: GenStep,
 }
 
-fn get_missions(seed: &FGlobalMissionSeed) {
-    let mut rand = FRandomStream::new(seed.random_seed as u32);
-    let season = data::ESeason::from_index(seed.season as usize);
+/// The generation step that failed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GenStep {
+    #[error("no biome left to pick")]
+    Biome,
+    #[error("no codename words to pick from")]
+    Name,
+    #[error("no mission template is allowed for the stage")]
+    MissionTemplate,
+    #[error("{0:?} is missing from the mission templates")]
+    UnknownMissionTemplate(EMissionTemplate),
+    #[error("{0:?} has no Deep Dive secondary objectives")]
+    DeepDiveObjective(EMissionTemplate),
+    #[error("every secondary objective of {0:?} is banned in the biome")]
+    SecondaryObjective(EMissionTemplate),
+    #[error("no mutator is compatible with the objectives")]
+    Mutator,
+    #[error("no warning is compatible with the objectives and the mutator")]
+    Warning,
+    #[error("{0:?} is missing from the DNA table")]
+    UnknownDna(EMissionDNA),
+    #[error("no DNA of {template:?} fits complexity {complexity:?} and duration {duration:?}")]
+    Dna {
+        template: EMissionTemplate,
+        complexity: Option<EMissionComplexity>,
+        duration: Option<EMissionDuration>,
+    },
+    #[error("{0:?} has no number of dreadnoughts")]
+    EliminationTargets(EObjective),
+}
+
+/// Seed of the 30 minute mission board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FGlobalMissionSeed {
+    pub random_seed: i32,
+    pub season: i32,
+    pub map_key: i32,
+}
 
-    let mut helpers = init_helpers(&mut rand);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("unknown season index {0}")]
+pub struct UnknownSeason(pub i32);
 
-    let mut extra_biomes = {
-        let range = data::get_mission_setup().extra_biomes;
-        let delta = range.max - range.min + 1;
-        if delta <= 0 {
-            0
-        } else {
-            range.rand(&mut rand)
+impl FGlobalMissionSeed {
+    /// Seed of the mission board that is live at `datetime`, in the season that was current at the
+    /// time.
+    pub fn from_datetime(datetime: OffsetDateTime) -> Self {
+        let datetime = datetime.to_offset(UtcOffset::UTC);
+        Self::from_datetime_in_season(datetime, ESeason::at(datetime.date()))
+    }
+
+    /// Seed of the mission board that is live at `datetime` for a player in `season`.
+    pub fn from_datetime_in_season(datetime: OffsetDateTime, season: ESeason) -> Self {
+        let datetime = datetime.to_offset(UtcOffset::UTC);
+        let year = datetime.year() as u32;
+        let month = datetime.month() as u32;
+        let day = datetime.day() as u32;
+        let hour = datetime.hour() as u32;
+        let minute = datetime.minute() as u32;
+
+        let random_seed = ((year.wrapping_mul(0x2a90af))
+            ^ (month.wrapping_mul(0x4f9ffb7))
+            ^ (day.wrapping_mul(0x73387))
+            ^ (hour.wrapping_mul(0x5b53f5))
+            ^ (minute / 30))
+            % 100000;
+
+        Self {
+            random_seed: random_seed as i32,
+            season: season as i32,
+            map_key: 0,
         }
-    };
+    }
 
-    rand.mutate(); // TODO unused?
-    let saved = rand.seed(); // surely there is some logical explanation here... forking rand stream?
-    let rand_helper = (rand.get_fraction() * helpers.len() as f32) as usize;
-    rand.set_seed(saved);
-    dbg!(rand_helper);
+    /// Seed of the mission board that is live at the given UTC unix timestamp (in seconds).
+    pub fn from_unix_timestamp(timestamp: i64) -> Result<Self, time::error::ComponentRange> {
+        OffsetDateTime::from_unix_timestamp(timestamp).map(Self::from_datetime)
+    }
+}
 
-    println!("{:X}", rand.seed());
-    dbg!(extra_biomes);
+/// Start of the 30 minute mission board window containing `datetime`, in UTC.
+pub fn mission_window_start(datetime: OffsetDateTime) -> OffsetDateTime {
+    let datetime = datetime.to_offset(UtcOffset::UTC);
+    let time = time::Time::from_hms(datetime.hour(), datetime.minute() / 30 * 30, 0)
+        .expect("hour and minute are taken from a valid time");
+    datetime.replace_time(time)
+}
 
-    match season.get().mission_map_event_zone_type {
-        data::ESeasonMissionMapOverlayType::None => {
-            let pick = &mut helpers[rand_helper];
+/// Iterates over successive 30 minute mission board windows, starting with the one containing
+/// `start`. Each item is the start of the window and the seed of its mission board.
+pub fn mission_windows(start: OffsetDateTime) -> MissionWindows {
+    MissionWindows {
+        next: mission_window_start(start),
+    }
+}
 
-            pick.picked_maybe = true;
+#[derive(Debug, Clone)]
+pub struct MissionWindows {
+    next: OffsetDateTime,
+}
 
-            for biome in pick.zone.get().biomes {
-                if extra_biomes <= 0 {
-                    break;
-                }
+impl Iterator for MissionWindows {
+    type Item = (OffsetDateTime, FGlobalMissionSeed);
 
-                if pick.biomes.contains(biome) {
-                    continue;
-                }
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next;
+        self.next = start.checked_add(Duration::minutes(30))?;
+        Some((start, FGlobalMissionSeed::from_datetime(start)))
+    }
+}
 
-                pick.biomes.push(*biome);
+/// Generates a regular (non Deep Dive) mission from its mission seed.
+///
+/// Everything derived from the mission seed is filled in: the secondary objective, the DNA and the
+/// objective details. Mutators and warnings are drawn from the mission board's stream instead and
+/// are left empty.
+///
+/// # Panics
+///
+/// If the data cannot generate the mission, see [`try_gen_mission`].
+pub fn gen_mission(
+    template: EMissionTemplate,
+    biome: EBiome,
+    seed: u32,
+    complexity_limit: Option<EMissionComplexity>,
+    duration_limit: Option<EMissionDuration>,
+) -> UGeneratedMission {
+    try_gen_mission(template, biome, seed, complexity_limit, duration_limit)
+        .unwrap_or_else(|step| panic!("{template:?} from seed {seed}: {step}"))
+}
 
-                extra_biomes -= 1;
-            }
+/// [`gen_mission`] that returns the step that failed instead of panicking.
+pub fn try_gen_mission(
+    template: EMissionTemplate,
+    biome: EBiome,
+    seed: u32,
+    complexity_limit: Option<EMissionComplexity>,
+    duration_limit: Option<EMissionDuration>,
+) -> Result<UGeneratedMission, GenStep> {
+    let data = GameVersion::LATEST.data();
+    let mission_template = &data.mission_template(template).mission_template;
+
+    let mut rand = FRandomStream::new(seed);
+    let secondary_objective =
+        select_secondary_objective(&data.bans, template, mission_template, biome, &mut rand)?;
+    let dna = select_dna(
+        data,
+        template,
+        mission_template,
+        complexity_limit,
+        duration_limit,
+        &mut rand,
+        &mut Trace::off(),
+    )?;
+
+    let mut mission = UGeneratedMission {
+        seed,
+        template,
+        biome,
+        primary_objective: ObjectiveInstance::from_objective(mission_template.primary_objective),
+        secondary_objectives: vec![ObjectiveInstance::from_objective(secondary_objective)],
+        warnings: vec![],
+        mutators: vec![],
+        complexity_limit,
+        duration_limit,
+        dna,
+    };
+    mission.initialize_objectives(&mut Trace::off())?;
+    Ok(mission)
+}
+
+fn select_secondary_objective(
+    bans: &BanRules,
+    template: EMissionTemplate,
+    mission_template: &UMissionTemplate,
+    biome: EBiome,
+    rand: &mut FRandomStream,
+) -> Result<EObjective, GenStep> {
+    let mut secondaries = mission_template.secondary_objectives.to_vec();
+    while !secondaries.is_empty() {
+        let secondary = rand.rand_remove(&mut secondaries);
+        if !bans.is_objective_banned_in_biome(secondary, biome) {
+            return Ok(secondary);
         }
-        data::ESeasonMissionMapOverlayType::Plague => {
-            todo!("plague regions")
+    }
+    Err(GenStep::SecondaryObjective(template))
+}
+
+fn select_dna(
+    data: &GenerationData,
+    template: EMissionTemplate,
+    mission_template: &UMissionTemplate,
+    complexity_limit: Option<EMissionComplexity>,
+    duration_limit: Option<EMissionDuration>,
+    rand: &mut FRandomStream,
+    trace: &mut Trace,
+) -> Result<EMissionDNA, GenStep> {
+    let mut possible_dna = vec![];
+    for dna in mission_template.dna {
+        let info = data.dna.get(dna).ok_or(GenStep::UnknownDna(*dna))?;
+        if (complexity_limit.is_none() || complexity_limit == Some(info.complexity))
+            && (duration_limit.is_none() || duration_limit == Some(info.duration))
+        {
+            possible_dna.push((*dna, info));
         }
     }
+    let total: f32 = possible_dna.iter().map(|(_, info)| info.weight).sum();
 
-    dbg!(&helpers);
+    let mut sum = 0.0;
+    let select = rand.get_fraction() * total;
 
-    // TODO verify when global_missions.len() > zones
-    let mut global_missions: Vec<_> = get_mission_setup()
-        .global_required_missions
+    let picked = possible_dna
         .iter()
-        .map(Some)
-        .chain(std::iter::repeat(None))
-        .take(helpers.len())
-        .collect();
-
-    dbg!(&global_missions);
-    shuffle(&mut rand, &mut global_missions);
-    dbg!(&global_missions);
+        .position(|(_, info)| {
+            sum += info.weight;
+            sum >= select
+        })
+        .ok_or(GenStep::Dna {
+            template,
+            complexity: complexity_limit,
+            duration: duration_limit,
+        })?;
+    trace.record(Step::Dna, rand, picked, || {
+        let excluded = mission_template
+            .dna
+            .iter()
+            .filter(|dna| possible_dna.iter().all(|(d, _)| d != *dna))
+            .map(|dna| (*dna, 0.0));
+        possible_dna
+            .iter()
+            .map(|(dna, info)| (*dna, info.weight))
+            .chain(excluded)
+            .map(|(dna, weight)| Candidate {
+                value: Choice::Dna(dna),
+                weight,
+            })
+            .collect()
+    });
+    Ok(possible_dna[picked].0)
 }
 
 fn deep_dive_get_mission(
@@ -204,11 +474,15 @@ fn deep_dive_get_mission(
     used_missions: &mut Vec<EMissionTemplate>,
     existing_missions: &[UGeneratedMission],
     rand: &mut FRandomStream,
-) -> (
-    EMissionTemplate,
-    Option<EMissionDuration>,
-    Option<EMissionComplexity>,
-) {
+    trace: &mut Trace,
+) -> Result<
+    (
+        EMissionTemplate,
+        Option<EMissionDuration>,
+        Option<EMissionComplexity>,
+    ),
+    GenStep,
+> {
     #[derive(Debug)]
     struct HelperStructA {
         mission: EMissionTemplate,
@@ -234,7 +508,7 @@ fn deep_dive_get_mission(
             continue;
         }
         if template.can_only_appear_once_per_deep_dive_set
-            && used_missions.iter().any(|m| *m == template.mission)
+            && used_missions.contains(&template.mission)
         {
             continue;
         }
@@ -281,138 +555,321 @@ fn deep_dive_get_mission(
     //    .collect::<Vec<_>>();
     //dbg!(d);
 
-    let mut probabilities = vec![];
-    for (i, item) in items.iter().enumerate() {
-        for _ in 0..item.probability {
-            probabilities.push(i);
-        }
-    }
-    let i = rand.rand_item(&probabilities);
-    let selected = &items[*i];
+    // same draw as picking from a list holding each item's index `probability` times
+    let total: u32 = items.iter().map(|item| item.probability).sum();
+    let mut ticket = rand.rand_helper(total as i32) as u32;
+    let picked = items
+        .iter()
+        .position(|item| {
+            let found = ticket < item.probability;
+            ticket = ticket.saturating_sub(item.probability);
+            found
+        })
+        .ok_or(GenStep::MissionTemplate)?;
+    trace.record(Step::Mission, rand, picked, || {
+        items
+            .iter()
+            .map(|item| Candidate {
+                value: Choice::Mission {
+                    template: item.mission,
+                    duration: item.duration,
+                    complexity: item.complexity,
+                },
+                weight: item.probability as f32,
+            })
+            .collect()
+    });
+    let selected = &items[picked];
 
     if selected.can_only_appear_once_per_deep_dive_set {
         used_missions.push(selected.mission);
     }
 
-    (selected.mission, selected.duration, selected.complexity)
+    Ok((selected.mission, selected.duration, selected.complexity))
 }
 
 fn select_mutator(
+    bans: &BanRules,
     mutators: &[EMissionMutator],
     primary_objective: EObjective,
     secondary_objectives: &[EObjective],
     rand: &mut FRandomStream,
-) -> EMissionMutator {
+    trace: &mut Trace,
+) -> Result<EMissionMutator, GenStep> {
     let mut pool = mutators.to_vec();
-    let mut i = pool.len() - 1;
-    loop {
+    for i in (0..pool.len()).rev() {
         let m = pool[i];
-        let incompatible = m.is_banned_objective(primary_objective)
+        let incompatible = bans.is_mutator_banned_with_objective(m, primary_objective)
             || secondary_objectives
                 .iter()
-                .any(|s| m.is_banned_objective(*s));
+                .any(|s| bans.is_mutator_banned_with_objective(m, *s));
 
         if incompatible {
             pool.swap_remove(i);
         }
-
-        if i == 0 {
-            break;
-        }
-        i -= 1;
     }
 
-    *rand.rand_item(&pool)
+    if pool.is_empty() {
+        return Err(GenStep::Mutator);
+    }
+    let picked = rand.rand_helper(pool.len() as i32) as usize;
+    trace.record(Step::Mutator, rand, picked, || {
+        let banned = mutators.iter().filter(|x| !pool.contains(x));
+        pool.iter()
+            .map(|x| Candidate::uniform(Choice::Mutator(*x)))
+            .chain(banned.map(|x| Candidate {
+                value: Choice::Mutator(*x),
+                weight: 0.0,
+            }))
+            .collect()
+    });
+    Ok(pool[picked])
 }
 
 fn select_warning(
+    bans: &BanRules,
     warnings: &[EMissionWarning],
     mutator: Option<EMissionMutator>,
     primary_objective: EObjective,
     secondary_objectives: &[EObjective],
     rand: &mut FRandomStream,
-) -> EMissionWarning {
+    trace: &mut Trace,
+) -> Result<EMissionWarning, GenStep> {
     let mut pool = warnings.to_vec();
-    let mut i = pool.len() - 1;
-    loop {
+    for i in (0..pool.len()).rev() {
         let w = pool[i];
-        let incompatible = w.is_banned_objective(primary_objective)
+        let incompatible = bans.is_warning_banned_with_objective(w, primary_objective)
             || secondary_objectives
                 .iter()
-                .any(|s| w.is_banned_objective(*s))
-            || mutator.map(|m| w.is_banned_mutator(m)).unwrap_or_default();
+                .any(|s| bans.is_warning_banned_with_objective(w, *s))
+            || mutator
+                .map(|m| bans.is_warning_banned_with_mutator(w, m))
+                .unwrap_or_default();
 
         if incompatible {
             pool.swap_remove(i);
         }
-
-        if i == 0 {
-            break;
-        }
-        i -= 1;
     }
 
-    *rand.rand_item(&pool)
+    if pool.is_empty() {
+        return Err(GenStep::Warning);
+    }
+    let picked = rand.rand_helper(pool.len() as i32) as usize;
+    trace.record(Step::Warning, rand, picked, || {
+        let banned = warnings.iter().filter(|x| !pool.contains(x));
+        pool.iter()
+            .map(|x| Candidate::uniform(Choice::Warning(*x)))
+            .chain(banned.map(|x| Candidate {
+                value: Choice::Warning(*x),
+                weight: 0.0,
+            }))
+            .collect()
+    });
+    Ok(pool[picked])
 }
 
 fn gen_deep_dive(
-    template: &UDeepDiveTemplate,
-    seed: u32,
+    data: &GenerationData,
+    kind: DeepDiveKind,
+    deep_dive_seed: u32,
     biome: EBiome,
     used_missions: &mut Vec<EMissionTemplate>,
-) -> UDeepDive {
-    let mut rand = FRandomStream::new(seed);
-    let first = rand.rand_item(data::names_first());
-    let last = rand.rand_item(data::names_last());
-    let name = format!("{first} {last}");
+) -> Result<UDeepDive, GenError> {
+    let mut stages = DeepDiveStages::new(data, kind, deep_dive_seed, biome, false)?;
+    for _ in 0..3 {
+        stages.next_stage(used_missions)?;
+    }
+    Ok(stages.finish())
+}
 
-    // mutators
-    let mut mutator_indexes = vec![0, 1, 2];
-    let mutator_count = sample_rand_interval(&mut rand, &template.mutator_count);
-    randomly_shrink(&mut rand, mutator_count as usize, &mut mutator_indexes);
+/// Generates the stages of a Deep Dive one at a time, so searches can stop at the first stage that
+/// does not match.
+struct DeepDiveStages<'a> {
+    data: &'a GenerationData,
+    kind: DeepDiveKind,
+    template: &'a UDeepDiveTemplate,
+    biome: EBiome,
+    name: (&'static str, &'static str),
+    rand: FRandomStream,
+    mutator_indexes: Vec<usize>,
+    warning_indexes: Vec<usize>,
+    mutators: Vec<EMissionMutator>,
+    warnings: Vec<EMissionWarning>,
+    stages: Vec<UGeneratedMission>,
+    trace: Trace,
+}
 
-    // warnings
-    let mut warning_indexes = vec![0, 1, 2];
-    let warning_count = sample_rand_interval(&mut rand, &template.warning_count);
-    randomly_shrink(&mut rand, warning_count as usize, &mut warning_indexes);
+impl<'a> DeepDiveStages<'a> {
+    fn new(
+        data: &'a GenerationData,
+        kind: DeepDiveKind,
+        deep_dive_seed: u32,
+        biome: EBiome,
+        explain: bool,
+    ) -> Result<Self, GenError> {
+        let template = match kind {
+            DeepDiveKind::Normal => data.normal_template,
+            DeepDiveKind::Elite => data.hard_template,
+        };
+        if data.names_first.is_empty() || data.names_last.is_empty() {
+            return Err(GenError {
+                kind,
+                stage: None,
+                step: GenStep::Name,
+            });
+        }
+        let mut trace = Trace::new(kind, explain);
+        let mut rand = FRandomStream::new(kind.dive_seed(deep_dive_seed));
+        let mut name = |rand: &mut FRandomStream, names: &'static [&'static str], step| {
+            let i = rand.rand_helper(names.len() as i32) as usize;
+            trace.record(step, rand, i, || {
+                names
+                    .iter()
+                    .map(|n| Candidate::uniform(Choice::Name(n)))
+                    .collect()
+            });
+            names[i]
+        };
+        let first = name(&mut rand, data.names_first, Step::FirstName);
+        let last = name(&mut rand, data.names_last, Step::LastName);
+
+        // mutators
+        let mut mutator_indexes = vec![0, 1, 2];
+        let mutator_count = sample_rand_interval(
+            &mut rand,
+            &template.mutator_count,
+            &mut trace,
+            (Step::MutatorInterval, Step::MutatorCount),
+        );
+        randomly_shrink(
+            &mut rand,
+            mutator_count as usize,
+            &mut mutator_indexes,
+            &mut trace,
+            Step::MutatorStageRemoved,
+        );
+
+        // warnings
+        let mut warning_indexes = vec![0, 1, 2];
+        let warning_count = sample_rand_interval(
+            &mut rand,
+            &template.warning_count,
+            &mut trace,
+            (Step::WarningInterval, Step::WarningCount),
+        );
+        randomly_shrink(
+            &mut rand,
+            warning_count as usize,
+            &mut warning_indexes,
+            &mut trace,
+            Step::WarningStageRemoved,
+        );
+
+        Ok(DeepDiveStages {
+            data,
+            kind,
+            template,
+            biome,
+            name: (first, last),
+            rand,
+            mutator_indexes,
+            warning_indexes,
+            mutators: data.deep_dive_settings.mutators.to_vec(),
+            warnings: data.deep_dive_settings.warnings.to_vec(),
+            stages: Vec::with_capacity(3),
+            trace,
+        })
+    }
+
+    fn name(&self) -> String {
+        format!("{} {}", self.name.0, self.name.1)
+    }
 
-    let mut mutators = get_deep_dive_settings().mutators.to_vec();
-    let mut warnings = get_deep_dive_settings().warnings.to_vec();
+    /// Generates the next of the 3 stages. `used_missions` is shared by both dives of a pair.
+    fn next_stage(
+        &mut self,
+        used_missions: &mut Vec<EMissionTemplate>,
+    ) -> Result<&UGeneratedMission, GenError> {
+        let i = self.stages.len();
+        self.trace.stage = Some(i);
+        let stage = self.gen_stage(used_missions).map_err(|step| GenError {
+            kind: self.kind,
+            stage: Some(i),
+            step,
+        })?;
+        self.stages.push(stage);
+        Ok(&self.stages[i])
+    }
+
+    fn gen_stage(
+        &mut self,
+        used_missions: &mut Vec<EMissionTemplate>,
+    ) -> Result<UGeneratedMission, GenStep> {
+        let Self {
+            data,
+            template,
+            biome,
+            rand,
+            mutator_indexes,
+            warning_indexes,
+            mutators,
+            warnings,
+            stages,
+            trace,
+            ..
+        } = self;
+        let i = stages.len();
 
-    let mut stages = vec![];
-    for i in 0..3 {
         let stage_template =
-            deep_dive_get_mission(template.missions, used_missions, &stages, &mut rand);
+            deep_dive_get_mission(template.missions, used_missions, stages, rand, trace)?;
 
         rand.mutate();
         let mission_seed = rand.seed();
         let mut mission_rand = FRandomStream::new(mission_seed);
-        let mission_template = &stage_template.0.get().mission_template;
+        let mission_template = &data
+            .mission_templates
+            .get(&stage_template.0)
+            .ok_or(GenStep::UnknownMissionTemplate(stage_template.0))?
+            .mission_template;
         let primary_objective = mission_template.primary_objective;
-        let secondary_objectives =
-            vec![*mission_rand.rand_item(mission_template.deep_dive_objectives)];
+        if mission_template.deep_dive_objectives.is_empty() {
+            return Err(GenStep::DeepDiveObjective(stage_template.0));
+        }
+        let objectives = mission_template.deep_dive_objectives;
+        let secondary = mission_rand.rand_helper(objectives.len() as i32) as usize;
+        trace.record(Step::SecondaryObjective, &mission_rand, secondary, || {
+            objectives
+                .iter()
+                .map(|o| Candidate::uniform(Choice::Objective(*o)))
+                .collect()
+        });
+        let secondary_objectives = vec![objectives[secondary]];
 
         let mut mutator = None;
         let mut warning = None;
 
         if mutator_indexes.contains(&i) {
             let r = select_mutator(
-                &mutators,
+                &data.bans,
+                mutators,
                 primary_objective,
                 &secondary_objectives,
-                &mut rand,
-            );
+                rand,
+                trace,
+            )?;
             mutators.swap_remove(mutators.iter().position(|i| *i == r).unwrap());
             mutator = Some(r)
         }
         if warning_indexes.contains(&i) {
             let r = select_warning(
-                &warnings,
+                &data.bans,
+                warnings,
                 mutator,
                 primary_objective,
                 &secondary_objectives,
-                &mut rand,
-            );
+                rand,
+                trace,
+            )?;
             warnings.swap_remove(warnings.iter().position(|i| *i == r).unwrap());
             warning = Some(r)
         }
@@ -423,44 +880,30 @@ fn gen_deep_dive(
         }
 
         let dna = {
-            let mut possible_dna = vec![];
-            for dna in stage_template.0.get().mission_template.dna {
-                if (stage_template.2.is_none() || stage_template.2 == Some(dna.get().complexity))
-                    && (stage_template.1.is_none() || stage_template.1 == Some(dna.get().duration))
-                {
-                    possible_dna.push(dna);
-                }
-            }
-            let total: f32 = possible_dna.iter().map(|d| d.get().weight).sum();
-
-            let mut sum = 0.0;
             let mut rand = FRandomStream::new(mission_seed);
-            {
-                // simulate normal secondary objective selection (result not used for DDs)
-                let mut secondaries = stage_template
-                    .0
-                    .get()
-                    .mission_template
-                    .secondary_objectives
-                    .to_vec();
-                while (rand.rand_remove(&mut secondaries)).is_banned_in_biome(biome) {}
-            }
-
-            let select = rand.get_fraction() * total;
-
-            **possible_dna
-                .iter()
-                .find(|d| {
-                    sum += d.get().weight;
-                    sum >= select
-                })
-                .unwrap()
+            // simulate normal secondary objective selection (result not used for DDs)
+            select_secondary_objective(
+                &data.bans,
+                stage_template.0,
+                mission_template,
+                *biome,
+                &mut rand,
+            )?;
+            select_dna(
+                data,
+                stage_template.0,
+                mission_template,
+                stage_template.2,
+                stage_template.1,
+                &mut rand,
+                trace,
+            )?
         };
 
         let mut stage = UGeneratedMission {
             seed: mission_seed,
             template: stage_template.0,
-            biome,
+            biome: *biome,
             primary_objective: ObjectiveInstance::from_objective(primary_objective),
             secondary_objectives: secondary_objectives
                 .iter()
@@ -472,39 +915,109 @@ fn gen_deep_dive(
             duration_limit: stage_template.1,
             dna,
         };
-        stage.initialize_objectives();
-
-        stages.push(stage);
+        stage.initialize_objectives(trace)?;
+        Ok(stage)
     }
 
-    UDeepDive {
-        name,
-        missions: stages,
-        biome,
+    fn finish(self) -> UDeepDive {
+        UDeepDive {
+            name: self.name(),
+            missions: self.stages,
+            biome: self.biome,
+        }
     }
 }
 
+/// Generates the Deep Dive pair for `seed`.
+///
+/// # Panics
+///
+/// If the data cannot generate the pair, see [`try_gen_deep_dive_pair`].
 pub fn gen_deep_dive_pair(seed: u32) -> (UDeepDive, UDeepDive) {
-    let deep_dive_seed = seed & 0x1ffff;
+    gen_deep_dive_pair_for(GameVersion::LATEST, seed)
+}
 
-    let mut rand = FRandomStream::new(deep_dive_seed);
-    let mut biomes = data::EBiome::VARIANTS.to_vec();
+/// Generates the Deep Dive pair for `seed` using the data of a specific game version.
+pub fn gen_deep_dive_pair_for(version: GameVersion, seed: u32) -> (UDeepDive, UDeepDive) {
+    gen_deep_dive_pair_with(version.data(), seed)
+}
+
+/// Generates the Deep Dive pair for `seed` from arbitrary generation data, such as a loaded
+/// [`DataPack`].
+pub fn gen_deep_dive_pair_with(data: &GenerationData, seed: u32) -> (UDeepDive, UDeepDive) {
+    try_gen_deep_dive_pair_with(data, seed).unwrap_or_else(|e| panic!("seed {seed}: {e}"))
+}
+
+/// [`gen_deep_dive_pair`] that returns the step that failed instead of panicking.
+pub fn try_gen_deep_dive_pair(seed: u32) -> Result<(UDeepDive, UDeepDive), GenError> {
+    try_gen_deep_dive_pair_for(GameVersion::LATEST, seed)
+}
+
+/// [`gen_deep_dive_pair_for`] that returns the step that failed instead of panicking.
+pub fn try_gen_deep_dive_pair_for(
+    version: GameVersion,
+    seed: u32,
+) -> Result<(UDeepDive, UDeepDive), GenError> {
+    try_gen_deep_dive_pair_with(version.data(), seed)
+}
+
+/// [`gen_deep_dive_pair_with`] that returns the step that failed instead of panicking.
+pub fn try_gen_deep_dive_pair_with(
+    data: &GenerationData,
+    seed: u32,
+) -> Result<(UDeepDive, UDeepDive), GenError> {
+    let deep_dive_seed = seed & 0x1ffff;
+    let (normal_biome, elite_biome) =
+        try_deep_dive_biomes(data, deep_dive_seed, &mut Trace::off())?;
 
     let mut used_missions = vec![];
 
     let normal = gen_deep_dive(
-        get_normal_template(),
-        deep_dive_seed ^ 0x929,
-        rand.rand_swap_remove(&mut biomes),
+        data,
+        DeepDiveKind::Normal,
+        deep_dive_seed,
+        normal_biome,
         &mut used_missions,
-    );
+    )?;
     let hard = gen_deep_dive(
-        get_hard_template(),
-        deep_dive_seed ^ 0x1300,
-        rand.rand_swap_remove(&mut biomes),
+        data,
+        DeepDiveKind::Elite,
+        deep_dive_seed,
+        elite_biome,
         &mut used_missions,
-    );
-    (normal, hard)
+    )?;
+    Ok((normal, hard))
+}
+
+/// Biomes of the normal and elite dive, drawn without replacement.
+fn try_deep_dive_biomes(
+    data: &GenerationData,
+    deep_dive_seed: u32,
+    trace: &mut Trace,
+) -> Result<(EBiome, EBiome), GenError> {
+    let mut rand = FRandomStream::new(deep_dive_seed);
+    let mut biomes = data.biomes.to_vec();
+    let mut draw = |kind| {
+        if biomes.is_empty() {
+            return Err(GenError {
+                kind,
+                stage: None,
+                step: GenStep::Biome,
+            });
+        }
+        let i = rand.rand_helper(biomes.len() as i32) as usize;
+        trace.kind = kind;
+        trace.record(Step::Biome, &rand, i, || {
+            biomes
+                .iter()
+                .map(|b| Candidate::uniform(Choice::Biome(*b)))
+                .collect()
+        });
+        Ok(biomes.swap_remove(i))
+    };
+    let normal = draw(DeepDiveKind::Normal)?;
+    let elite = draw(DeepDiveKind::Elite)?;
+    Ok((normal, elite))
 }
 
 #[cfg(test)]
@@ -514,24 +1027,107 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_gen() {
-        let seed = FGlobalMissionSeed {
-            random_seed: 2,
-            season: 5,
-            map_key: 5,
-        };
+    fn test_gen_mission_secondary() {
+        for seed in 0..1000 {
+            for template in EMissionTemplate::VARIANTS {
+                for biome in EBiome::VARIANTS {
+                    let mission = gen_mission(*template, *biome, seed, None, None);
+                    let [secondary] = &mission.secondary_objectives[..] else {
+                        panic!("expected exactly one secondary objective");
+                    };
+                    let secondary = secondary.objective();
+                    assert!(template
+                        .get()
+                        .mission_template
+                        .secondary_objectives
+                        .contains(&secondary));
+                    assert!(!secondary.is_banned_in_biome(*biome));
+                }
+            }
+        }
+    }
 
-        get_missions(&seed);
+    #[test]
+    fn test_seasons() {
+        use time::macros::{date, datetime};
+
+        for season in ESeason::VARIANTS {
+            assert_eq!(ESeason::from_index(*season as i32), Ok(*season));
+            assert_eq!(season.get().season, *season);
+        }
+        assert_eq!(ESeason::from_index(-1), Err(UnknownSeason(-1)));
+        let unknown = ESeason::VARIANTS.len() as i32;
+        assert_eq!(ESeason::from_index(unknown), Err(UnknownSeason(unknown)));
+
+        assert_eq!(ESeason::at(date!(2020 - 05 - 13)), ESeason::Season0);
+        assert_eq!(ESeason::at(date!(2022 - 12 - 24)), ESeason::Season3);
+        assert_eq!(ESeason::at(date!(2024 - 07 - 13)), ESeason::Season5);
+        assert_eq!(
+            FGlobalMissionSeed::from_datetime(datetime!(2023-01-01 12:00 UTC)).season,
+            ESeason::Season3 as i32
+        );
     }
 
     #[test]
-    fn test_deep_dive() {
-        let seed_v2 = 83255885;
-        let deep_dive_seed = seed_v2 & 0x1ffff;
-        let deep_dive_seed = 122106;
-        //let deep_dive_seed = 37731;
-        //let deep_dive_seed = 71169;
+    fn test_game_version() {
+        for seed in [0, 2048, 66099, 0x1ffff] {
+            assert_eq!(
+                gen_deep_dive_pair_for(GameVersion::LATEST, seed),
+                gen_deep_dive_pair(seed)
+            );
+        }
+        for version in GameVersion::VARIANTS {
+            let data = version.data();
+            for template in EMissionTemplate::VARIANTS {
+                for dna in data.mission_template(*template).mission_template.dna {
+                    assert!(data.dna.contains_key(dna), "{version:?} {dna:?}");
+                }
+            }
+        }
+    }
 
+    #[test]
+    fn test_try_gen_deep_dive_pair() {
+        assert_eq!(
+            try_gen_deep_dive_pair(216).unwrap(),
+            gen_deep_dive_pair(216)
+        );
+
+        // every stage of the normal dive gets a mutator, but all of them are banned
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.normal_template.mutator_count = vec![RandInterval {
+            weight: 1.0,
+            min: 3,
+            max: 3,
+        }];
+        let primaries: Vec<_> = pack
+            .mission_templates
+            .values()
+            .map(|t| t.primary_objective)
+            .collect();
+        for mutator in &pack.deep_dive_mutators {
+            pack.bans
+                .mutator_objectives
+                .insert(*mutator, primaries.clone());
+        }
+        let data = pack.load().unwrap();
+        let err = try_gen_deep_dive_pair_with(data, 216).unwrap_err();
+        assert_eq!(
+            err,
+            GenError {
+                kind: DeepDiveKind::Normal,
+                stage: Some(0),
+                step: GenStep::Mutator,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Normal Deep Dive stage 1: no mutator is compatible with the objectives"
+        );
+    }
+
+    #[test]
+    fn test_deep_dive() {
         let mut count_a = 0;
         let mut count_b = 0;
         for seed in 0..1000 {
@@ -561,45 +1157,209 @@ mod test {
         //dbg!(hard);
     }
 
+    /// Checks the rules the generator enforces on a Deep Dive pair, returning the first broken one.
+    fn check_deep_dive_invariants(seed: u32) -> Result<(), String> {
+        let data = GameVersion::LATEST.data();
+        let (normal, hard) = gen_deep_dive_pair(seed);
+        // (mission, whether it counts against the whole pair) of every stage of the pair
+        let mut set_missions = vec![];
+        for (template, dive) in [(data.normal_template, &normal), (data.hard_template, &hard)] {
+            let name = &dive.name;
+            if dive.missions.len() != 3 {
+                return Err(format!("{name}: {} stages", dive.missions.len()));
+            }
+            for pair in dive.missions.windows(2) {
+                if pair[0].template == pair[1].template {
+                    return Err(format!("{name}: consecutive {:?}", pair[0].template));
+                }
+            }
+
+            for mission in &dive.missions {
+                let item = template
+                    .missions
+                    .iter()
+                    .find(|item| {
+                        item.mission == mission.template
+                            && mission
+                                .duration_limit
+                                .is_none_or(|d| item.allowed_durations.contains(&d))
+                            && mission
+                                .complexity_limit
+                                .is_none_or(|c| item.allowed_complexities.contains(&c))
+                    })
+                    .ok_or_else(|| {
+                        format!("{name}: {:?} is not in the template", mission.template)
+                    })?;
+                let count = dive
+                    .missions
+                    .iter()
+                    .filter(|m| m.template == mission.template)
+                    .count();
+                if item.can_only_appear_once && count > 1 {
+                    return Err(format!("{name}: {:?} appears {count} times", item.mission));
+                }
+                if item.can_only_appear_once_per_deep_dive_set {
+                    if set_missions.contains(&mission.template) {
+                        return Err(format!(
+                            "{name}: {:?} appears again in the pair",
+                            item.mission
+                        ));
+                    }
+                    set_missions.push(mission.template);
+                }
+
+                let objectives: Vec<_> = std::iter::once(&mission.primary_objective)
+                    .chain(&mission.secondary_objectives)
+                    .map(ObjectiveInstance::objective)
+                    .collect();
+                for mutator in &mission.mutators {
+                    if let Some(o) = objectives.iter().find(|o| mutator.is_banned_objective(**o)) {
+                        return Err(format!("{name}: {mutator:?} with {o:?}"));
+                    }
+                }
+                for warning in &mission.warnings {
+                    if let Some(o) = objectives.iter().find(|o| warning.is_banned_objective(**o)) {
+                        return Err(format!("{name}: {warning:?} with {o:?}"));
+                    }
+                    if let Some(m) = mission
+                        .mutators
+                        .iter()
+                        .find(|m| warning.is_banned_mutator(**m))
+                    {
+                        return Err(format!("{name}: {warning:?} with {m:?}"));
+                    }
+                }
+            }
+
+            for (what, interval, count) in [
+                (
+                    "mutators",
+                    &template.mutator_count,
+                    dive.missions
+                        .iter()
+                        .map(|m| m.mutators.len())
+                        .sum::<usize>(),
+                ),
+                (
+                    "warnings",
+                    &template.warning_count,
+                    dive.missions
+                        .iter()
+                        .map(|m| m.warnings.len())
+                        .sum::<usize>(),
+                ),
+            ] {
+                let count = count as i32;
+                let in_range = interval
+                    .intervals
+                    .iter()
+                    .any(|i| (i.range.min..=i.range.max).contains(&count));
+                if !in_range {
+                    return Err(format!("{name}: {count} {what}, outside of {interval:?}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_deep_dive_invariants() {
+        // seeds drawn from a stream so failures are reproducible, set DEEP_DIVE_TEST_SEED to try
+        // other samples
+        let base = std::env::var("DEEP_DIVE_TEST_SEED")
+            .map(|s| s.parse().expect("DEEP_DIVE_TEST_SEED is a u32"))
+            .unwrap_or(0x5eed);
+        let mut rand = FRandomStream::new(base);
+        let samples = (0..2000).map(|_| {
+            rand.mutate();
+            rand.seed()
+        });
+        for seed in [0, 0x1ffff].into_iter().chain(samples) {
+            if let Err(broken) = check_deep_dive_invariants(seed) {
+                panic!("seed {seed} (sampled from DEEP_DIVE_TEST_SEED={base}): {broken}");
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "generates every Deep Dive, run with --release"]
+    fn test_deep_dive_invariants_all_seeds() {
+        for seed in 0..DEEP_DIVE_SEED_COUNT {
+            if let Err(broken) = check_deep_dive_invariants(seed) {
+                panic!("seed {seed}: {broken}");
+            }
+        }
+    }
+
     #[test]
     fn test_global_mission_seed() {
-        let now = time::OffsetDateTime::now_utc();
-        let month = now.month() as u32;
-        let day = now.day() as u32;
-        let year = now.year() as u32;
-        let hour = now.hour() as u32;
-        let minute = now.minute() as u32;
-
-        let seed = ((year.wrapping_mul(0x2a90af))
-            ^ (month.wrapping_mul(0x4f9ffb7))
-            ^ (day.wrapping_mul(0x73387))
-            ^ (hour.wrapping_mul(0x5b53f5))
-            ^ (minute / 30))
-            % 100000;
-        dbg!(seed);
+        use time::macros::datetime;
+
+        let seed = FGlobalMissionSeed::from_datetime(datetime!(2024-07-13 20:00 UTC));
+        assert_eq!(seed.random_seed, 86310);
+        assert_eq!(
+            FGlobalMissionSeed::from_datetime(datetime!(2024-07-13 20:45 UTC)).random_seed,
+            86311
+        );
+        assert_eq!(
+            FGlobalMissionSeed::from_datetime(datetime!(2024-07-13 22:00 +02:00)),
+            seed
+        );
+        assert_eq!(
+            FGlobalMissionSeed::from_unix_timestamp(
+                datetime!(2024-07-13 20:00 UTC).unix_timestamp()
+            )
+            .unwrap(),
+            seed
+        );
+
+        let windows: Vec<_> = mission_windows(datetime!(2024-07-13 20:17:42 UTC))
+            .take(3)
+            .collect();
+        assert_eq!(
+            windows.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+            [
+                datetime!(2024-07-13 20:00 UTC),
+                datetime!(2024-07-13 20:30 UTC),
+                datetime!(2024-07-13 21:00 UTC),
+            ]
+        );
+        assert_eq!(
+            windows
+                .iter()
+                .map(|(_, seed)| seed.random_seed)
+                .collect::<Vec<_>>(),
+            [86310, 86311, 31835]
+        );
     }
 
     #[test]
     fn compare_dds() {
-        #[derive(Debug, Deserialize)]
-        struct DD {
-            missions: Vec<UGeneratedMission>,
-        }
+        /// Entry of the corpus. Pairs recorded from the game by the CLI's `record` command only
+        /// hold the cleaned stages, its `compare_corpus` test checks those.
         #[derive(Debug, Deserialize)]
         struct DDPair {
             seed: u32,
-            normal: DD,
-            hard: DD,
+            normal: serde_json::Value,
+            hard: serde_json::Value,
         }
-        let dds: Vec<DDPair> =
-            serde_json::from_slice(&std::fs::read("test_data/dds.json").unwrap()).unwrap();
-        //println!("{:#?}", dds);
-
-        for dd in dds {
-            let (normal, hard) = gen_deep_dive_pair(dd.seed);
-            println!("seed = {}", dd.seed);
-            pretty_assertions::assert_eq!(dd.normal.missions, normal.missions, "normal");
-            pretty_assertions::assert_eq!(dd.hard.missions, hard.missions, "hard");
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_data/dds.json");
+        let dds: Vec<DDPair> = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        let dds: Vec<(u32, UDeepDive, UDeepDive)> = dds
+            .into_iter()
+            .filter_map(|dd| {
+                let normal = serde_json::from_value(dd.normal).ok()?;
+                let hard = serde_json::from_value(dd.hard).ok()?;
+                Some((dd.seed, normal, hard))
+            })
+            .collect();
+        assert!(!dds.is_empty());
+
+        for (seed, dd_normal, dd_hard) in dds {
+            let (normal, hard) = gen_deep_dive_pair(seed);
+            println!("seed = {seed}");
+            pretty_assertions::assert_eq!(dd_normal, normal, "normal");
+            pretty_assertions::assert_eq!(dd_hard, hard, "hard");
         }
     }
 
@@ -704,4 +1464,4 @@ mod test {
             );
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,311 @@
+//! Reverse lookup of Deep Dive seeds from what is visible in game.
+//!
+//! The codename is drawn first from the dive's stream, so checking it takes two draws per seed and
+//! rejects almost every seed before anything else has to be generated.
+//!
+//! A stage's mission seed is drawn from the same stream a few draws later, so stepping the stream
+//! back from a mission seed leads to the dive seed without trying every seed.
+
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+use thiserror::Error;
+
+use crate::explain::Trace;
+use crate::rand::{seed_before, FRandomStream};
+use crate::{
+    try_deep_dive_biomes, try_gen_deep_dive_pair_with, EBiome, EMissionTemplate, GameVersion,
+    GenError,
+};
+
+/// Number of distinct Deep Dive seeds, only the lower 17 bits of a seed are used.
+pub const DEEP_DIVE_SEED_COUNT: u32 = 0x20000;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, VariantArray, Serialize, Deserialize,
+)]
+pub enum DeepDiveKind {
+    Normal,
+    Elite,
+}
+
+impl DeepDiveKind {
+    /// Seed of this dive's own stream, derived from the seed of the pair.
+    pub fn dive_seed(self, deep_dive_seed: u32) -> u32 {
+        match self {
+            DeepDiveKind::Normal => deep_dive_seed ^ 0x929,
+            DeepDiveKind::Elite => deep_dive_seed ^ 0x1300,
+        }
+    }
+}
+
+/// What is known about a single Deep Dive, e.g. from a screenshot. Anything left as `None` matches
+/// every dive.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DeepDiveObservation {
+    pub kind: Option<DeepDiveKind>,
+    /// Full codename as shown in game, such as "Angry Overhang".
+    pub name: Option<String>,
+    /// First word of the codename.
+    pub first_name: Option<String>,
+    /// Last word of the codename.
+    pub last_name: Option<String>,
+    pub biome: Option<EBiome>,
+    pub stages: [Option<EMissionTemplate>; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DeepDiveMatch {
+    pub seed: u32,
+    pub kind: DeepDiveKind,
+}
+
+/// A stage of a Deep Dive, see [`find_deep_dive_seeds_by_mission_seed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MissionSeedMatch {
+    pub seed: u32,
+    pub kind: DeepDiveKind,
+    /// Stage 0 to 2.
+    pub stage: usize,
+}
+
+/// Upper bound on the draws from a dive's stream before its last mission seed. The built-in
+/// versions take 10 to 19 depending on the stage templates, mutators and warnings.
+const MAX_MISSION_SEED_DRAWS: u32 = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LookupError {
+    #[error("{0:?} is not a Deep Dive codename")]
+    UnknownName(String),
+    #[error("{0:?} is not the first word of any Deep Dive codename")]
+    UnknownFirstName(String),
+    #[error("{0:?} is not the last word of any Deep Dive codename")]
+    UnknownLastName(String),
+    #[error(transparent)]
+    Gen(#[from] GenError),
+}
+
+/// Returns every seed in `0..DEEP_DIVE_SEED_COUNT` whose normal or elite dive matches
+/// `observation`, ordered by seed.
+pub fn find_deep_dive_seeds(
+    observation: &DeepDiveObservation,
+) -> Result<Vec<DeepDiveMatch>, LookupError> {
+    let data = GameVersion::LATEST.data();
+    let names = NameFilter::new(data.names_first, data.names_last, observation)?;
+    let kinds = match observation.kind {
+        Some(kind) => vec![kind],
+        None => DeepDiveKind::VARIANTS.to_vec(),
+    };
+    let check_stages = observation.stages.iter().any(Option::is_some);
+
+    let mut matches = vec![];
+    for seed in 0..DEEP_DIVE_SEED_COUNT {
+        for &kind in &kinds {
+            let mut rand = FRandomStream::new(kind.dive_seed(seed));
+            let first = rand.rand_helper(data.names_first.len() as i32) as usize;
+            let last = rand.rand_helper(data.names_last.len() as i32) as usize;
+            if !names.matches(first, last) {
+                continue;
+            }
+
+            if let Some(biome) = observation.biome {
+                let biomes = try_deep_dive_biomes(data, seed, &mut Trace::off())?;
+                let drawn = match kind {
+                    DeepDiveKind::Normal => biomes.0,
+                    DeepDiveKind::Elite => biomes.1,
+                };
+                if drawn != biome {
+                    continue;
+                }
+            }
+
+            if check_stages {
+                // the elite dive depends on the missions used by the normal one, so generate both
+                let (normal, elite) = try_gen_deep_dive_pair_with(data, seed)?;
+                let dive = match kind {
+                    DeepDiveKind::Normal => normal,
+                    DeepDiveKind::Elite => elite,
+                };
+                let stages_match = observation
+                    .stages
+                    .iter()
+                    .zip(&dive.missions)
+                    .all(|(stage, mission)| stage.is_none_or(|t| t == mission.template));
+                if !stages_match {
+                    continue;
+                }
+            }
+
+            matches.push(DeepDiveMatch { seed, kind });
+        }
+    }
+    Ok(matches)
+}
+
+/// Returns the Deep Dive stages whose `UGeneratedMission::seed` is `mission_seed`, ordered by
+/// seed. There is usually exactly one.
+pub fn find_deep_dive_seeds_by_mission_seed(
+    mission_seed: u32,
+) -> Result<Vec<MissionSeedMatch>, LookupError> {
+    find_deep_dive_seeds_by_mission_seed_for(GameVersion::LATEST, mission_seed)
+}
+
+/// Same as [`find_deep_dive_seeds_by_mission_seed`] with the data of a specific game version.
+pub fn find_deep_dive_seeds_by_mission_seed_for(
+    version: GameVersion,
+    mission_seed: u32,
+) -> Result<Vec<MissionSeedMatch>, LookupError> {
+    let data = version.data();
+    let mut matches = vec![];
+    for draws in 1..=MAX_MISSION_SEED_DRAWS {
+        let dive_seed = seed_before(mission_seed, draws);
+        for &kind in DeepDiveKind::VARIANTS {
+            // dive seeds are the pair seed with a few low bits flipped, which is its own inverse
+            let seed = kind.dive_seed(dive_seed);
+            if seed >= DEEP_DIVE_SEED_COUNT {
+                continue;
+            }
+            // the number of draws depends on the whole dive, so only generating it tells whether
+            // the mission seed really is one of its stages
+            let (normal, elite) = try_gen_deep_dive_pair_with(data, seed)?;
+            let dive = match kind {
+                DeepDiveKind::Normal => normal,
+                DeepDiveKind::Elite => elite,
+            };
+            if let Some(stage) = dive.missions.iter().position(|m| m.seed == mission_seed) {
+                matches.push(MissionSeedMatch { seed, kind, stage });
+            }
+        }
+    }
+    matches.sort();
+    matches.dedup();
+    Ok(matches)
+}
+
+/// Accepted codename word indexes.
+struct NameFilter {
+    /// Exact (first, last) pairs of a full codename.
+    pairs: Option<Vec<(usize, usize)>>,
+    first: Option<Vec<usize>>,
+    last: Option<Vec<usize>>,
+}
+
+impl NameFilter {
+    fn new(
+        names_first: &[&str],
+        names_last: &[&str],
+        observation: &DeepDiveObservation,
+    ) -> Result<Self, LookupError> {
+        let pairs = match &observation.name {
+            Some(name) => {
+                let name = name.trim();
+                let mut pairs = vec![];
+                for (i, first) in names_first.iter().enumerate() {
+                    for (j, last) in names_last.iter().enumerate() {
+                        if name.eq_ignore_ascii_case(&format!("{first} {last}")) {
+                            pairs.push((i, j));
+                        }
+                    }
+                }
+                if pairs.is_empty() {
+                    return Err(LookupError::UnknownName(name.to_string()));
+                }
+                Some(pairs)
+            }
+            None => None,
+        };
+        let first = match &observation.first_name {
+            Some(word) => Some(
+                Self::word_indexes(names_first, word)
+                    .ok_or_else(|| LookupError::UnknownFirstName(word.clone()))?,
+            ),
+            None => None,
+        };
+        let last = match &observation.last_name {
+            Some(word) => Some(
+                Self::word_indexes(names_last, word)
+                    .ok_or_else(|| LookupError::UnknownLastName(word.clone()))?,
+            ),
+            None => None,
+        };
+        Ok(NameFilter { pairs, first, last })
+    }
+
+    fn word_indexes(names: &[&str], word: &str) -> Option<Vec<usize>> {
+        let word = word.trim();
+        let indexes: Vec<_> = (0..names.len())
+            .filter(|i| names[*i].eq_ignore_ascii_case(word))
+            .collect();
+        (!indexes.is_empty()).then_some(indexes)
+    }
+
+    fn matches(&self, first: usize, last: usize) -> bool {
+        self.pairs
+            .as_ref()
+            .is_none_or(|p| p.contains(&(first, last)))
+            && self.first.as_ref().is_none_or(|f| f.contains(&first))
+            && self.last.as_ref().is_none_or(|l| l.contains(&last))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_deep_dive_pair;
+
+    #[test]
+    fn test_find_deep_dive_seeds_by_mission_seed() {
+        for seed in [0, 216, 0x1ffff] {
+            let (normal, elite) = gen_deep_dive_pair(seed);
+            for (kind, dive) in [(DeepDiveKind::Normal, normal), (DeepDiveKind::Elite, elite)] {
+                for (stage, mission) in dive.missions.iter().enumerate() {
+                    let found = find_deep_dive_seeds_by_mission_seed(mission.seed).unwrap();
+                    assert!(
+                        found.contains(&MissionSeedMatch { seed, kind, stage }),
+                        "{seed} {kind:?} {stage}: {found:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_deep_dive_seeds() {
+        for seed in [0, 2048, 66099, 0x1ffff] {
+            let (normal, elite) = gen_deep_dive_pair(seed);
+            for (kind, dive) in [(DeepDiveKind::Normal, normal), (DeepDiveKind::Elite, elite)] {
+                let observation = DeepDiveObservation {
+                    name: Some(dive.name.to_uppercase()),
+                    biome: Some(dive.biome),
+                    stages: [None, Some(dive.missions[1].template), None],
+                    ..Default::default()
+                };
+                let matches = find_deep_dive_seeds(&observation).unwrap();
+                assert!(matches.contains(&DeepDiveMatch { seed, kind }));
+
+                for m in matches {
+                    let (normal, elite) = gen_deep_dive_pair(m.seed);
+                    let found = match m.kind {
+                        DeepDiveKind::Normal => normal,
+                        DeepDiveKind::Elite => elite,
+                    };
+                    assert_eq!(found.name, dive.name);
+                    assert_eq!(found.biome, dive.biome);
+                    assert_eq!(found.missions[1].template, dive.missions[1].template);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        let observation = DeepDiveObservation {
+            first_name: Some("Leaf Lover's".to_string()),
+            last_name: Some("Nope".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            find_deep_dive_seeds(&observation),
+            Err(LookupError::UnknownLastName("Nope".to_string()))
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! German names, written by hand and not checked against the game yet.
+
+use super::Catalog;
+use crate::{EBiome, EDreadnought, EMissionMutator, EMissionWarning, EObjective, Resource};
+
+pub(super) const CATALOG: Catalog = Catalog {
+    biomes: &[
+        (EBiome::BIOME_CrystalCaves, "Kristallhöhlen"),
+        (EBiome::BIOME_FungusBogs, "Pilzsümpfe"),
+        (EBiome::BIOME_MagmaCaves, "Magmakern"),
+        (EBiome::BIOME_RadioactiveZone, "Radioaktive Sperrzone"),
+        (EBiome::BIOME_LushDownpour, "Dichte Biozone"),
+        (
+            EBiome::BIOME_SandblastedCorridors,
+            "Sandgestrahlte Korridore",
+        ),
+        (EBiome::BIOME_SaltCaves, "Salzgruben"),
+        (EBiome::BIOME_IceCaves, "Gletscherschichten"),
+        (EBiome::BIOME_AzureWeald, "Azurwald"),
+        (EBiome::BIOME_HollowBough, "Hohler Ast"),
+    ],
+    objectives: &[
+        (EObjective::OBJ_1st_DeepScan, "Tiefenscan"),
+        (EObjective::OBJ_1st_Escort, "Eskorte"),
+        (EObjective::OBJ_1st_Extraction, "Bergbauexpedition"),
+        (EObjective::OBJ_1st_Facility, "Industriesabotage"),
+        (EObjective::OBJ_1st_Gather_AlienEggs, "Eierjagd"),
+        (EObjective::OBJ_1st_PointExtraction, "Punktförderung"),
+        (EObjective::OBJ_1st_Refinery, "Raffinerie vor Ort"),
+        (EObjective::OBJ_1st_Salvage, "Bergungsoperation"),
+        (EObjective::OBJ_Eliminate_Eggs, "Eliminierung"),
+        (EObjective::OBJ_Excavation_C, "Schwerabbau"),
+        (EObjective::OBJ_DD_AlienEggs, "Eier"),
+        (EObjective::OBJ_DD_DeepScan, "Resonanzkristalle"),
+        (EObjective::OBJ_DD_Defense, "Blackbox"),
+        (EObjective::OBJ_DD_Elimination_Eggs, "Dreadnought"),
+        (EObjective::OBJ_DD_Morkite, "Morkit"),
+        (EObjective::OBJ_DD_MorkiteWell, "Flüssigmorkit-Quelle"),
+        (EObjective::OBJ_DD_RepairMinimules, "Mini-M.U.L.E.s"),
+        (EObjective::OBJ_DD_Excavation, "Resinitmasse"),
+    ],
+    mutators: &[
+        (
+            EMissionMutator::MMUT_ExplosiveEnemies,
+            "Explosive Innereien",
+        ),
+        (EMissionMutator::MMUT_ExterminationContract, "Goldene Käfer"),
+        (EMissionMutator::MMUT_SecretSecondary, "Geheimes Nebenziel"),
+        (EMissionMutator::MMUT_XXXP, "Doppelte EP"),
+        (EMissionMutator::MMUT_GoldRush, "Goldrausch"),
+        (EMissionMutator::MMUT_OxygenRich, "Reiche Atmosphäre"),
+        (EMissionMutator::MMUT_RichInMinerals, "Mineralienrausch"),
+        (EMissionMutator::MMUT_Weakspot, "Kritische Schwäche"),
+        (EMissionMutator::MMUT_BloodSugar, "Blutzucker"),
+        (EMissionMutator::MMUT_LowGravity, "Geringe Schwerkraft"),
+    ],
+    warnings: &[
+        (
+            EMissionWarning::WRN_RegenerativeEnemies,
+            "Regenerierende Käfer",
+        ),
+        (EMissionWarning::WRN_HeroEnemies, "Elitebedrohung"),
+        (EMissionWarning::WRN_MacteraCave, "Mactera-Plage"),
+        (EMissionWarning::WRN_RockInfestation, "Ebonit-Ausbruch"),
+        (EMissionWarning::WRN_BulletHell, "In Deckung!"),
+        (EMissionWarning::WRN_CaveLeechDen, "Höhlenegel-Nest"),
+        (EMissionWarning::WRN_NoOxygen, "Sauerstoffmangel"),
+        (EMissionWarning::WRN_Plague, "Lithophagen-Ausbruch"),
+        (EMissionWarning::WRN_ExploderInfestation, "Exploder-Befall"),
+        (EMissionWarning::WRN_Ghost, "Spukhöhle"),
+        (EMissionWarning::WRN_LethalEnemies, "Tödliche Gegner"),
+        (EMissionWarning::WRN_NoShields, "Schildstörung"),
+        (EMissionWarning::WRN_InfestedEnemies, "Parasiten"),
+        (EMissionWarning::WRN_Swarmagedon, "Schwarmageddon"),
+        (EMissionWarning::WRN_RivalIncursion, "Rivalen-Präsenz"),
+    ],
+    resources: &[
+        (Resource::Morkite, &["Morkit", "Morkit"]),
+        (Resource::AlienEgg, &["Ei", "Eier"]),
+        (Resource::Aquarq, &["Aquarq", "Aquarqs"]),
+        (Resource::MiniMule, &["Mini-M.U.L.E.", "Mini-M.U.L.E.s"]),
+        (
+            Resource::ResonanceCrystal,
+            &["Resonanzkristall", "Resonanzkristalle"],
+        ),
+        (Resource::DeepScan, &["Tiefenscan", "Tiefenscans"]),
+        (Resource::ResiniteMass, &["Resinitmasse", "Resinitmassen"]),
+        (Resource::Dreadnought, &["Dreadnought", "Dreadnoughts"]),
+    ],
+    plural: |amount| usize::from(amount != 1),
+    perform: "{} durchführen",
+    uplink: "{} & Uplink",
+    dreadnoughts: &[
+        (EDreadnought::Dreadnought, "Klassisch"),
+        (EDreadnought::Hiveguard, "Stockwächter"),
+        (EDreadnought::Twins, "Zwillinge"),
+    ],
+    none: "Keine",
+};
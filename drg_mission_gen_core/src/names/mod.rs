@@ -0,0 +1,387 @@
+//! Names of biomes, missions, objectives, mutators and warnings as the game shows them.
+//!
+//! Values without a known name, like objectives that never appear in a Deep Dive, fall back to
+//! their internal name. Names in other languages come from a catalog per [`Language`] and fall
+//! back to English where the catalog has no translation.
+//!
+//! The catalogs are written by hand, not extracted from the game's `Game.locres`, so a name can
+//! differ from the one shown in game. The least reliable ones, the Ossuary Depths biome and the Pit
+//! Jaw Colony and Scrab Nesting Grounds warnings, are left out and stay English. Codenames are
+//! always English.
+
+mod de;
+mod pt_br;
+mod ru;
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+
+use crate::{
+    EBiome, EDreadnought, EMissionMutator, EMissionTemplate, EMissionWarning, EObjective,
+    ObjectiveRequirement, Resource,
+};
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, VariantArray, Serialize, Deserialize,
+)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Russian,
+    BrazilianPortuguese,
+}
+
+impl Language {
+    fn catalog(self) -> Option<&'static Catalog> {
+        match self {
+            Language::English => None,
+            Language::German => Some(&de::CATALOG),
+            Language::Russian => Some(&ru::CATALOG),
+            Language::BrazilianPortuguese => Some(&pt_br::CATALOG),
+        }
+    }
+
+    /// Shown for a stage without a mutator or warning, e.g. `None`.
+    pub fn none(self) -> &'static str {
+        self.catalog().map_or("None", |c| c.none)
+    }
+}
+
+/// Translated names of one language.
+struct Catalog {
+    biomes: &'static [(EBiome, &'static str)],
+    objectives: &'static [(EObjective, &'static str)],
+    mutators: &'static [(EMissionMutator, &'static str)],
+    warnings: &'static [(EMissionWarning, &'static str)],
+    /// Forms of a resource name, indexed by [`Catalog::plural`].
+    resources: &'static [(Resource, &'static [&'static str])],
+    /// Index of the form of a resource name to use for an amount.
+    plural: fn(u32) -> usize,
+    /// Deep Scan goal, `{}` stands for the scans, e.g. `Perform {}`.
+    perform: &'static str,
+    /// Salvage Operation goal, `{}` stands for the Mini-M.U.L.E.s, e.g. `{} & Uplink`.
+    uplink: &'static str,
+    dreadnoughts: &'static [(EDreadnought, &'static str)],
+    /// Stands for a missing mutator or warning.
+    none: &'static str,
+}
+
+fn find<K: PartialEq, V: Copy>(entries: &[(K, V)], key: K) -> Option<V> {
+    entries.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+impl EBiome {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EBiome::BIOME_CrystalCaves => "Crystalline Caverns",
+            EBiome::BIOME_FungusBogs => "Fungus Bogs",
+            EBiome::BIOME_MagmaCaves => "Magma Core",
+            EBiome::BIOME_RadioactiveZone => "Radioactive Exclusion Zone",
+            EBiome::BIOME_LushDownpour => "Dense Biozone",
+            EBiome::BIOME_SandblastedCorridors => "Sandblasted Corridors",
+            EBiome::BIOME_SaltCaves => "Salt Pits",
+            EBiome::BIOME_IceCaves => "Glacial Strata",
+            EBiome::BIOME_AzureWeald => "Azure Weald",
+            EBiome::BIOME_HollowBough => "Hollow Bough",
+            EBiome::BIOME_OssuaryDepths => "Ossuary Depths",
+        }
+    }
+
+    pub fn display_name_in(self, language: Language) -> &'static str {
+        language
+            .catalog()
+            .and_then(|c| find(c.biomes, self))
+            .unwrap_or_else(|| self.display_name())
+    }
+}
+
+impl EMissionTemplate {
+    pub fn display_name(self) -> &'static str {
+        self.display_name_in(Language::English)
+    }
+
+    pub fn display_name_in(self, language: Language) -> &'static str {
+        self.get()
+            .mission_template
+            .primary_objective
+            .display_name_in(language)
+    }
+}
+
+impl EObjective {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EObjective::OBJ_1st_DeepScan => "Deep Scan",
+            EObjective::OBJ_1st_Escort => "Escort Duty",
+            EObjective::OBJ_1st_Extraction => "Mining Expedition",
+            EObjective::OBJ_1st_Facility => "Industrial Sabotage",
+            EObjective::OBJ_1st_Gather_AlienEggs => "Egg Hunt",
+            EObjective::OBJ_1st_PointExtraction => "Point Extraction",
+            EObjective::OBJ_1st_Refinery => "On-Site Refinery",
+            EObjective::OBJ_1st_Salvage => "Salvage Operation",
+            EObjective::OBJ_Eliminate_Eggs => "Elimination",
+            EObjective::OBJ_Excavation_C => "Heavy Extraction",
+            EObjective::OBJ_2nd_DestroyBhaBarnacles => "Bha Barnacles",
+            EObjective::OBJ_2nd_DestroyEggs => "Alien Eggs",
+            EObjective::OBJ_2nd_Find_ApocaBloom => "Apoca Blooms",
+            EObjective::OBJ_2nd_Find_BooloCap => "Boolo Caps",
+            EObjective::OBJ_2nd_Find_Ebonut => "Ebonuts",
+            EObjective::OBJ_2nd_Find_Fossil => "Fossils",
+            EObjective::OBJ_2nd_Find_Gunkseed => "Gunk Seeds",
+            EObjective::OBJ_2nd_KillFleas => "Fester Fleas",
+            EObjective::OBJ_2nd_Mine_Dystrum => "Dystrum",
+            EObjective::OBJ_2nd_Mine_Hollomite => "Hollomite",
+            EObjective::OBJ_DD_AlienEggs => "Eggs",
+            EObjective::OBJ_DD_DeepScan => "Resonance Crystals",
+            EObjective::OBJ_DD_Defense => "Black Box",
+            EObjective::OBJ_DD_Elimination_Eggs => "Dreadnought",
+            EObjective::OBJ_DD_Morkite => "Morkite",
+            EObjective::OBJ_DD_MorkiteWell => "Liquid Morkite Well",
+            EObjective::OBJ_DD_RepairMinimules => "Mini-M.U.L.E.s",
+            EObjective::OBJ_DD_Excavation => "Resinite Mass",
+            other => other.into(),
+        }
+    }
+
+    pub fn display_name_in(self, language: Language) -> &'static str {
+        language
+            .catalog()
+            .and_then(|c| find(c.objectives, self))
+            .unwrap_or_else(|| self.display_name())
+    }
+
+    /// What the objective asks for to collect `requirement`, e.g. `Perform 3 Deep Scans`.
+    pub fn goal_in(self, requirement: ObjectiveRequirement, language: Language) -> String {
+        let requirement = requirement.display_in(language);
+        let catalog = language.catalog();
+        let template = match self {
+            EObjective::OBJ_1st_DeepScan => catalog.map_or("Perform {}", |c| c.perform),
+            EObjective::OBJ_1st_Salvage => catalog.map_or("{} & Uplink", |c| c.uplink),
+            _ => return requirement,
+        };
+        template.replace("{}", &requirement)
+    }
+}
+
+impl EMissionMutator {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EMissionMutator::MMUT_ExplosiveEnemies => "Volatile Guts",
+            EMissionMutator::MMUT_ExterminationContract => "Golden Bugs",
+            EMissionMutator::MMUT_SecretSecondary => "Secret Secondary",
+            EMissionMutator::MMUT_XXXP => "Double XP",
+            EMissionMutator::MMUT_GoldRush => "Gold Rush",
+            EMissionMutator::MMUT_OxygenRich => "Rich Atmosphere",
+            EMissionMutator::MMUT_RichInMinerals => "Mineral Mania",
+            EMissionMutator::MMUT_Weakspot => "Critical Weakness",
+            EMissionMutator::MMUT_BloodSugar => "Blood Sugar",
+            EMissionMutator::MMUT_LowGravity => "Low Gravity",
+        }
+    }
+
+    pub fn display_name_in(self, language: Language) -> &'static str {
+        language
+            .catalog()
+            .and_then(|c| find(c.mutators, self))
+            .unwrap_or_else(|| self.display_name())
+    }
+}
+
+impl EMissionWarning {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EMissionWarning::WRN_RegenerativeEnemies => "Regenerative Bugs",
+            EMissionWarning::WRN_HeroEnemies => "Elite Threat",
+            EMissionWarning::WRN_MacteraCave => "Mactera Plague",
+            EMissionWarning::WRN_RockInfestation => "Ebonite Outbreak",
+            EMissionWarning::WRN_BulletHell => "Duck and Cover",
+            EMissionWarning::WRN_CaveLeechDen => "Cave Leech Cluster",
+            EMissionWarning::WRN_NoOxygen => "Low Oxygen",
+            EMissionWarning::WRN_Plague => "Lithophage Outbreak",
+            EMissionWarning::WRN_ExploderInfestation => "Exploder Infestation",
+            EMissionWarning::WRN_Ghost => "Haunted Cave",
+            EMissionWarning::WRN_LethalEnemies => "Lethal Enemies",
+            EMissionWarning::WRN_NoShields => "Shield Disruption",
+            EMissionWarning::WRN_InfestedEnemies => "Parasites",
+            EMissionWarning::WRN_Swarmagedon => "Swarmageddon",
+            EMissionWarning::WRN_RivalIncursion => "Rival Presence",
+            EMissionWarning::WRN_PitJawColony => "Pit Jaw Colony",
+            EMissionWarning::WRN_ScrabNestingGrounds => "Scrab Nesting Grounds",
+        }
+    }
+
+    pub fn display_name_in(self, language: Language) -> &'static str {
+        language
+            .catalog()
+            .and_then(|c| find(c.warnings, self))
+            .unwrap_or_else(|| self.display_name())
+    }
+}
+
+impl Resource {
+    /// Name for `amount` of the resource, singular for 1.
+    pub fn display_name(self, amount: u32) -> &'static str {
+        let (singular, plural) = match self {
+            Resource::Morkite => ("Morkite", "Morkite"),
+            Resource::AlienEgg => ("Egg", "Eggs"),
+            Resource::Aquarq => ("Aquarq", "Aquarqs"),
+            Resource::MiniMule => ("Mini-M.U.L.E.", "Mini-M.U.L.E.s"),
+            Resource::ResonanceCrystal => ("Resonance Crystal", "Resonance Crystals"),
+            Resource::DeepScan => ("Deep Scan", "Deep Scans"),
+            Resource::ResiniteMass => ("Resinite Mass", "Resinite Masses"),
+            Resource::Dreadnought => ("Dreadnought", "Dreadnoughts"),
+        };
+        if amount == 1 {
+            singular
+        } else {
+            plural
+        }
+    }
+
+    /// Name for `amount` of the resource in the grammatical number `language` uses for it.
+    pub fn display_name_in(self, amount: u32, language: Language) -> &'static str {
+        language
+            .catalog()
+            .and_then(|c| find(c.resources, self)?.get((c.plural)(amount)).copied())
+            .unwrap_or_else(|| self.display_name(amount))
+    }
+}
+
+impl EDreadnought {
+    /// Short name used to tell the dreadnoughts of an Elimination apart, e.g. `Hiveguard`.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            EDreadnought::Dreadnought => "Classic",
+            EDreadnought::Hiveguard => "Hiveguard",
+            EDreadnought::Twins => "Twins",
+        }
+    }
+
+    pub fn display_name_in(self, language: Language) -> &'static str {
+        language
+            .catalog()
+            .and_then(|c| find(c.dreadnoughts, self))
+            .unwrap_or_else(|| self.display_name())
+    }
+}
+
+impl ObjectiveRequirement {
+    pub fn display_in(self, language: Language) -> String {
+        format!(
+            "{} {}",
+            self.amount,
+            self.resource.display_name_in(self.amount, language)
+        )
+    }
+}
+
+/// E.g. `400 Morkite`.
+impl fmt::Display for ObjectiveRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_in(Language::English))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{EMissionDNA, GameVersion};
+
+    #[test]
+    fn test_requirement_names() {
+        let requirement = EMissionDNA::DNA_2_05.get().requirement.unwrap();
+        assert_eq!(requirement.to_string(), "400 Morkite");
+        assert_eq!(
+            EObjective::OBJ_DD_RepairMinimules
+                .deep_dive_requirement()
+                .unwrap()
+                .to_string(),
+            "2 Mini-M.U.L.E.s"
+        );
+        assert_eq!(Resource::AlienEgg.display_name(1), "Egg");
+        assert_eq!(
+            EMissionTemplate::MissionType_Refinery.display_name(),
+            "On-Site Refinery"
+        );
+    }
+
+    #[test]
+    fn test_requirements_complete() {
+        let data = GameVersion::LATEST.data();
+        for template in EMissionTemplate::VARIANTS {
+            let dna = data.mission_template(*template).mission_template.dna;
+            let with_amount = dna
+                .iter()
+                .filter(|d| data.dna(**d).requirement.is_some())
+                .count();
+            assert!(
+                with_amount == 0 || with_amount == dna.len(),
+                "{template:?} has DNA with and without a requirement"
+            );
+        }
+        // not used by any template, so the game never shows an amount for it
+        assert!(EMissionTemplate::VARIANTS.iter().all(|t| !data
+            .mission_template(*t)
+            .mission_template
+            .dna
+            .contains(&EMissionDNA::DNA_Web_Large)));
+    }
+
+    #[test]
+    fn test_catalogs() {
+        for &language in Language::VARIANTS {
+            let Some(catalog) = language.catalog() else {
+                continue;
+            };
+            for mutator in EMissionMutator::VARIANTS {
+                assert!(
+                    catalog.mutators.iter().any(|(m, _)| m == mutator),
+                    "{mutator:?}"
+                );
+            }
+            for dreadnought in EDreadnought::VARIANTS {
+                assert!(
+                    catalog.dreadnoughts.iter().any(|(d, _)| d == dreadnought),
+                    "{dreadnought:?}"
+                );
+            }
+            for resource in Resource::VARIANTS {
+                let forms = find(catalog.resources, *resource).unwrap();
+                for amount in 0..1000 {
+                    assert!(
+                        (catalog.plural)(amount) < forms.len(),
+                        "{resource:?} {amount}"
+                    );
+                }
+            }
+        }
+
+        let requirement = ObjectiveRequirement {
+            resource: Resource::DeepScan,
+            amount: 3,
+        };
+        assert_eq!(
+            EObjective::OBJ_1st_DeepScan.goal_in(requirement, Language::English),
+            "Perform 3 Deep Scans"
+        );
+        assert_eq!(
+            EObjective::OBJ_1st_DeepScan.goal_in(requirement, Language::German),
+            "3 Tiefenscans durchführen"
+        );
+        assert_eq!(
+            Resource::AlienEgg.display_name_in(5, Language::Russian),
+            "яиц"
+        );
+        assert_eq!(
+            EObjective::OBJ_2nd_Find_Fossil.display_name_in(Language::German),
+            "Fossils"
+        );
+        assert_eq!(
+            EMissionWarning::WRN_PitJawColony.display_name_in(Language::Russian),
+            "Pit Jaw Colony"
+        );
+    }
+}
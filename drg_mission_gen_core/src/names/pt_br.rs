@@ -0,0 +1,106 @@
+//! Brazilian Portuguese names, written by hand and not checked against the game yet.
+
+use super::Catalog;
+use crate::{EBiome, EDreadnought, EMissionMutator, EMissionWarning, EObjective, Resource};
+
+pub(super) const CATALOG: Catalog = Catalog {
+    biomes: &[
+        (EBiome::BIOME_CrystalCaves, "Cavernas Cristalinas"),
+        (EBiome::BIOME_FungusBogs, "Pântanos Fúngicos"),
+        (EBiome::BIOME_MagmaCaves, "Núcleo de Magma"),
+        (EBiome::BIOME_RadioactiveZone, "Zona de Exclusão Radioativa"),
+        (EBiome::BIOME_LushDownpour, "Biozona Densa"),
+        (EBiome::BIOME_SandblastedCorridors, "Corredores Jateados"),
+        (EBiome::BIOME_SaltCaves, "Poços de Sal"),
+        (EBiome::BIOME_IceCaves, "Estratos Glaciais"),
+        (EBiome::BIOME_AzureWeald, "Bosque Azul"),
+        (EBiome::BIOME_HollowBough, "Galho Oco"),
+    ],
+    objectives: &[
+        (EObjective::OBJ_1st_DeepScan, "Varredura Profunda"),
+        (EObjective::OBJ_1st_Escort, "Escolta"),
+        (EObjective::OBJ_1st_Extraction, "Expedição de Mineração"),
+        (EObjective::OBJ_1st_Facility, "Sabotagem Industrial"),
+        (EObjective::OBJ_1st_Gather_AlienEggs, "Caça aos Ovos"),
+        (EObjective::OBJ_1st_PointExtraction, "Extração Pontual"),
+        (EObjective::OBJ_1st_Refinery, "Refino no Local"),
+        (EObjective::OBJ_1st_Salvage, "Operação de Resgate"),
+        (EObjective::OBJ_Eliminate_Eggs, "Eliminação"),
+        (EObjective::OBJ_Excavation_C, "Extração Pesada"),
+        (EObjective::OBJ_DD_AlienEggs, "Ovos"),
+        (EObjective::OBJ_DD_DeepScan, "Cristais de Ressonância"),
+        (EObjective::OBJ_DD_Defense, "Caixa-Preta"),
+        (EObjective::OBJ_DD_Elimination_Eggs, "Dreadnought"),
+        (EObjective::OBJ_DD_Morkite, "Morkita"),
+        (EObjective::OBJ_DD_MorkiteWell, "Poço de Morkita Líquida"),
+        (EObjective::OBJ_DD_RepairMinimules, "Mini-M.U.L.E.s"),
+        (EObjective::OBJ_DD_Excavation, "Massa de Resinita"),
+    ],
+    mutators: &[
+        (EMissionMutator::MMUT_ExplosiveEnemies, "Entranhas Voláteis"),
+        (
+            EMissionMutator::MMUT_ExterminationContract,
+            "Insetos Dourados",
+        ),
+        (EMissionMutator::MMUT_SecretSecondary, "Secundário Secreto"),
+        (EMissionMutator::MMUT_XXXP, "XP em Dobro"),
+        (EMissionMutator::MMUT_GoldRush, "Corrida do Ouro"),
+        (EMissionMutator::MMUT_OxygenRich, "Atmosfera Rica"),
+        (EMissionMutator::MMUT_RichInMinerals, "Mania Mineral"),
+        (EMissionMutator::MMUT_Weakspot, "Fraqueza Crítica"),
+        (EMissionMutator::MMUT_BloodSugar, "Açúcar no Sangue"),
+        (EMissionMutator::MMUT_LowGravity, "Baixa Gravidade"),
+    ],
+    warnings: &[
+        (
+            EMissionWarning::WRN_RegenerativeEnemies,
+            "Insetos Regenerativos",
+        ),
+        (EMissionWarning::WRN_HeroEnemies, "Ameaça de Elite"),
+        (EMissionWarning::WRN_MacteraCave, "Praga de Mactera"),
+        (EMissionWarning::WRN_RockInfestation, "Surto de Ebonita"),
+        (EMissionWarning::WRN_BulletHell, "Abaixem-se!"),
+        (EMissionWarning::WRN_CaveLeechDen, "Ninho de Sanguessugas"),
+        (EMissionWarning::WRN_NoOxygen, "Pouco Oxigênio"),
+        (EMissionWarning::WRN_Plague, "Surto de Litófagos"),
+        (
+            EMissionWarning::WRN_ExploderInfestation,
+            "Infestação de Explosivos",
+        ),
+        (EMissionWarning::WRN_Ghost, "Caverna Assombrada"),
+        (EMissionWarning::WRN_LethalEnemies, "Inimigos Letais"),
+        (EMissionWarning::WRN_NoShields, "Interferência de Escudo"),
+        (EMissionWarning::WRN_InfestedEnemies, "Parasitas"),
+        (EMissionWarning::WRN_Swarmagedon, "Enxamegedom"),
+        (EMissionWarning::WRN_RivalIncursion, "Presença Rival"),
+    ],
+    resources: &[
+        (Resource::Morkite, &["Morkita", "Morkita"]),
+        (Resource::AlienEgg, &["Ovo", "Ovos"]),
+        (Resource::Aquarq, &["Aquarq", "Aquarqs"]),
+        (Resource::MiniMule, &["Mini-M.U.L.E.", "Mini-M.U.L.E.s"]),
+        (
+            Resource::ResonanceCrystal,
+            &["Cristal de Ressonância", "Cristais de Ressonância"],
+        ),
+        (
+            Resource::DeepScan,
+            &["Varredura Profunda", "Varreduras Profundas"],
+        ),
+        (
+            Resource::ResiniteMass,
+            &["Massa de Resinita", "Massas de Resinita"],
+        ),
+        (Resource::Dreadnought, &["Dreadnought", "Dreadnoughts"]),
+    ],
+    // Singular for 0 and 1.
+    plural: |amount| usize::from(amount > 1),
+    perform: "Realizar {}",
+    uplink: "{} e Uplink",
+    dreadnoughts: &[
+        (EDreadnought::Dreadnought, "Clássico"),
+        (EDreadnought::Hiveguard, "Guardião da Colmeia"),
+        (EDreadnought::Twins, "Gêmeos"),
+    ],
+    none: "Nenhum",
+};
@@ -0,0 +1,130 @@
+//! Russian names, written by hand and not checked against the game yet.
+
+use super::Catalog;
+use crate::{EBiome, EDreadnought, EMissionMutator, EMissionWarning, EObjective, Resource};
+
+pub(super) const CATALOG: Catalog = Catalog {
+    biomes: &[
+        (EBiome::BIOME_CrystalCaves, "Кристальные пещеры"),
+        (EBiome::BIOME_FungusBogs, "Грибные болота"),
+        (EBiome::BIOME_MagmaCaves, "Магматическое ядро"),
+        (
+            EBiome::BIOME_RadioactiveZone,
+            "Радиоактивная зона отчуждения",
+        ),
+        (EBiome::BIOME_LushDownpour, "Плотная биозона"),
+        (EBiome::BIOME_SandblastedCorridors, "Песчаные коридоры"),
+        (EBiome::BIOME_SaltCaves, "Соляные ямы"),
+        (EBiome::BIOME_IceCaves, "Ледниковые пласты"),
+        (EBiome::BIOME_AzureWeald, "Лазурная чаща"),
+        (EBiome::BIOME_HollowBough, "Полая ветвь"),
+    ],
+    objectives: &[
+        (EObjective::OBJ_1st_DeepScan, "Глубокое сканирование"),
+        (EObjective::OBJ_1st_Escort, "Сопровождение"),
+        (EObjective::OBJ_1st_Extraction, "Горная экспедиция"),
+        (EObjective::OBJ_1st_Facility, "Промышленный саботаж"),
+        (EObjective::OBJ_1st_Gather_AlienEggs, "Охота за яйцами"),
+        (EObjective::OBJ_1st_PointExtraction, "Точечная добыча"),
+        (EObjective::OBJ_1st_Refinery, "Переработка на месте"),
+        (EObjective::OBJ_1st_Salvage, "Спасательная операция"),
+        (EObjective::OBJ_Eliminate_Eggs, "Уничтожение"),
+        (EObjective::OBJ_Excavation_C, "Тяжёлая добыча"),
+        (EObjective::OBJ_DD_AlienEggs, "Яйца"),
+        (EObjective::OBJ_DD_DeepScan, "Резонансные кристаллы"),
+        (EObjective::OBJ_DD_Defense, "Чёрный ящик"),
+        (EObjective::OBJ_DD_Elimination_Eggs, "Дредноут"),
+        (EObjective::OBJ_DD_Morkite, "Моркит"),
+        (EObjective::OBJ_DD_MorkiteWell, "Скважина жидкого моркита"),
+        (EObjective::OBJ_DD_RepairMinimules, "Мини-M.U.L.E."),
+        (EObjective::OBJ_DD_Excavation, "Резинитовая масса"),
+    ],
+    mutators: &[
+        (
+            EMissionMutator::MMUT_ExplosiveEnemies,
+            "Взрывные внутренности",
+        ),
+        (EMissionMutator::MMUT_ExterminationContract, "Золотые жуки"),
+        (EMissionMutator::MMUT_SecretSecondary, "Секретная задача"),
+        (EMissionMutator::MMUT_XXXP, "Двойной опыт"),
+        (EMissionMutator::MMUT_GoldRush, "Золотая лихорадка"),
+        (EMissionMutator::MMUT_OxygenRich, "Богатая атмосфера"),
+        (EMissionMutator::MMUT_RichInMinerals, "Минеральная мания"),
+        (EMissionMutator::MMUT_Weakspot, "Критическая слабость"),
+        (EMissionMutator::MMUT_BloodSugar, "Сахар в крови"),
+        (EMissionMutator::MMUT_LowGravity, "Низкая гравитация"),
+    ],
+    warnings: &[
+        (
+            EMissionWarning::WRN_RegenerativeEnemies,
+            "Регенерирующие жуки",
+        ),
+        (EMissionWarning::WRN_HeroEnemies, "Элитная угроза"),
+        (EMissionWarning::WRN_MacteraCave, "Чума мактер"),
+        (EMissionWarning::WRN_RockInfestation, "Вспышка эбонита"),
+        (EMissionWarning::WRN_BulletHell, "Ложись!"),
+        (EMissionWarning::WRN_CaveLeechDen, "Гнездо пещерных пиявок"),
+        (EMissionWarning::WRN_NoOxygen, "Недостаток кислорода"),
+        (EMissionWarning::WRN_Plague, "Вспышка литофагов"),
+        (
+            EMissionWarning::WRN_ExploderInfestation,
+            "Нашествие взрывунов",
+        ),
+        (EMissionWarning::WRN_Ghost, "Пещера с призраком"),
+        (EMissionWarning::WRN_LethalEnemies, "Смертоносные враги"),
+        (EMissionWarning::WRN_NoShields, "Отключение щитов"),
+        (EMissionWarning::WRN_InfestedEnemies, "Паразиты"),
+        (EMissionWarning::WRN_Swarmagedon, "Свармагеддон"),
+        (
+            EMissionWarning::WRN_RivalIncursion,
+            "Присутствие конкурентов",
+        ),
+    ],
+    resources: &[
+        (Resource::Morkite, &["моркит", "моркита", "моркита"]),
+        (Resource::AlienEgg, &["яйцо", "яйца", "яиц"]),
+        (Resource::Aquarq, &["акварк", "акварка", "акварков"]),
+        (
+            Resource::MiniMule,
+            &["Мини-M.U.L.E.", "Мини-M.U.L.E.", "Мини-M.U.L.E."],
+        ),
+        (
+            Resource::ResonanceCrystal,
+            &[
+                "резонансный кристалл",
+                "резонансных кристалла",
+                "резонансных кристаллов",
+            ],
+        ),
+        (
+            Resource::DeepScan,
+            &[
+                "глубокое сканирование",
+                "глубоких сканирования",
+                "глубоких сканирований",
+            ],
+        ),
+        (
+            Resource::ResiniteMass,
+            &["резинитовая масса", "резинитовые массы", "резинитовых масс"],
+        ),
+        (
+            Resource::Dreadnought,
+            &["дредноут", "дредноута", "дредноутов"],
+        ),
+    ],
+    // One, few and many.
+    plural: |amount| match (amount % 10, amount % 100) {
+        (1, n) if n != 11 => 0,
+        (2..=4, n) if !(12..=14).contains(&n) => 1,
+        _ => 2,
+    },
+    perform: "Выполнить {}",
+    uplink: "{} и аплинк",
+    dreadnoughts: &[
+        (EDreadnought::Dreadnought, "Классический"),
+        (EDreadnought::Hiveguard, "Страж улья"),
+        (EDreadnought::Twins, "Близнецы"),
+    ],
+    none: "Нет",
+};
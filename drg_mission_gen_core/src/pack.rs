@@ -0,0 +1,605 @@
+//! Data packs: generation data loaded at runtime from JSON or TOML instead of the compiled-in
+//! tables, so a game patch can be followed by editing a data file.
+//!
+//! [`DataPack::from_version`] exports the tables of a built-in [`GameVersion`] as a starting point.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::data::{FRandIntervalItem, UDeepDiveSettings};
+use crate::{
+    BanRules, EBiome, EMissionComplexity, EMissionDNA, EMissionDuration, EMissionMutator,
+    EMissionTemplate, EMissionWarning, EObjective, FDeepDiveTemplateItem, FIRandRange,
+    FMissionTemplateItem, FRandInterval, GameVersion, GenerationData, ObjectiveRequirement,
+    UDeepDiveTemplate, UMissionDNA, UMissionTemplate,
+};
+
+/// Every pack loaded so far with the data it was turned into.
+static LOADED: Mutex<Vec<(DataPack, &'static GenerationData)>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Error)]
+pub enum DataPackError {
+    #[error("invalid JSON data pack: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid TOML data pack: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("a data pack needs at least two biomes, found {0}")]
+    NotEnoughBiomes(usize),
+    #[error("mission template {0:?} is referenced but not defined")]
+    MissingMissionTemplate(EMissionTemplate),
+    #[error("mission DNA {0:?} is referenced but not defined")]
+    MissingDna(EMissionDNA),
+    #[error("mission template {0:?} has no {1}")]
+    EmptyMissionTemplate(EMissionTemplate, &'static str),
+    #[error("{0} is empty")]
+    Empty(&'static str),
+    #[error("{0} needs at least {1} entries, found {2}")]
+    TooShort(&'static str, usize, usize),
+    #[error("{0} has an interval from {1} to {2}, outside of 0 to {3}")]
+    IntervalOutOfRange(&'static str, i32, i32, usize),
+    #[error("{0} has a negative weight")]
+    NegativeWeight(&'static str),
+    #[error("{0} has no positive weight")]
+    NoWeight(&'static str),
+    #[error("mission DNA {0:?} has a negative weight")]
+    NegativeDnaWeight(EMissionDNA),
+    #[error("Deep Dive mission {0:?} allows {1}, which no DNA of it with a weight has")]
+    NoMatchingDna(EMissionTemplate, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataPack {
+    /// Biomes a Deep Dive pair draws from, in draw order.
+    pub biomes: Vec<EBiome>,
+    pub mission_templates: BTreeMap<EMissionTemplate, MissionTemplate>,
+    pub dna: BTreeMap<EMissionDNA, Dna>,
+    pub normal_template: DeepDiveTemplate,
+    pub hard_template: DeepDiveTemplate,
+    pub deep_dive_mutators: Vec<EMissionMutator>,
+    pub deep_dive_warnings: Vec<EMissionWarning>,
+    #[serde(default)]
+    pub bans: BanRules,
+    pub names_first: Vec<String>,
+    pub names_last: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MissionTemplate {
+    pub rarity: f32,
+    pub primary_objective: EObjective,
+    pub secondary_objectives: Vec<EObjective>,
+    pub deep_dive_objectives: Vec<EObjective>,
+    pub dna: Vec<EMissionDNA>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dna {
+    pub duration: EMissionDuration,
+    pub complexity: EMissionComplexity,
+    pub weight: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<ObjectiveRequirement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeepDiveTemplate {
+    pub mutator_count: Vec<RandInterval>,
+    pub warning_count: Vec<RandInterval>,
+    pub missions: Vec<DeepDiveMission>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RandInterval {
+    pub weight: f32,
+    pub min: i32,
+    pub max: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeepDiveMission {
+    pub mission: EMissionTemplate,
+    pub probability: u32,
+    #[serde(default)]
+    pub allowed_durations: Vec<EMissionDuration>,
+    #[serde(default)]
+    pub allowed_complexities: Vec<EMissionComplexity>,
+    #[serde(default)]
+    pub can_only_appear_once: bool,
+    #[serde(default)]
+    pub can_only_appear_once_per_deep_dive_set: bool,
+}
+
+impl DataPack {
+    pub fn from_json(json: &str) -> Result<Self, DataPackError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, DataPackError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Exports the compiled-in tables of `version`.
+    pub fn from_version(version: GameVersion) -> Self {
+        let data = version.data();
+        DataPack {
+            biomes: data.biomes.to_vec(),
+            mission_templates: data
+                .mission_templates
+                .iter()
+                .map(|(k, t)| {
+                    let m = &t.mission_template;
+                    let template = MissionTemplate {
+                        rarity: t.rarity,
+                        primary_objective: m.primary_objective,
+                        secondary_objectives: m.secondary_objectives.to_vec(),
+                        deep_dive_objectives: m.deep_dive_objectives.to_vec(),
+                        dna: m.dna.to_vec(),
+                    };
+                    (*k, template)
+                })
+                .collect(),
+            dna: data
+                .dna
+                .iter()
+                .map(|(k, d)| {
+                    let dna = Dna {
+                        duration: d.duration,
+                        complexity: d.complexity,
+                        weight: d.weight,
+                        requirement: d.requirement,
+                    };
+                    (*k, dna)
+                })
+                .collect(),
+            normal_template: DeepDiveTemplate::from_template(data.normal_template),
+            hard_template: DeepDiveTemplate::from_template(data.hard_template),
+            deep_dive_mutators: data.deep_dive_settings.mutators.to_vec(),
+            deep_dive_warnings: data.deep_dive_settings.warnings.to_vec(),
+            bans: data.bans.clone(),
+            names_first: data.names_first.iter().map(|n| n.to_string()).collect(),
+            names_last: data.names_last.iter().map(|n| n.to_string()).collect(),
+        }
+    }
+
+    /// Checks that generation can not run out of entries or hit an undefined reference.
+    pub fn validate(&self) -> Result<(), DataPackError> {
+        if self.biomes.len() < 2 {
+            return Err(DataPackError::NotEnoughBiomes(self.biomes.len()));
+        }
+        // each of the three stages can take one mutator and one warning out of the pool
+        if self.deep_dive_mutators.len() < 3 {
+            return Err(DataPackError::TooShort(
+                "deep_dive_mutators",
+                3,
+                self.deep_dive_mutators.len(),
+            ));
+        }
+        if self.deep_dive_warnings.len() < 3 {
+            return Err(DataPackError::TooShort(
+                "deep_dive_warnings",
+                3,
+                self.deep_dive_warnings.len(),
+            ));
+        }
+        if self.names_first.is_empty() {
+            return Err(DataPackError::Empty("names_first"));
+        }
+        if self.names_last.is_empty() {
+            return Err(DataPackError::Empty("names_last"));
+        }
+        for (name, counts, template) in [
+            (
+                "normal_template",
+                [
+                    "normal_template.mutator_count",
+                    "normal_template.warning_count",
+                ],
+                &self.normal_template,
+            ),
+            (
+                "hard_template",
+                ["hard_template.mutator_count", "hard_template.warning_count"],
+                &self.hard_template,
+            ),
+        ] {
+            if template.missions.is_empty() {
+                return Err(DataPackError::Empty(name));
+            }
+            if template.mutator_count.is_empty() || template.warning_count.is_empty() {
+                return Err(DataPackError::Empty(name));
+            }
+            // the counts pick stages, each of which takes one entry out of the pool
+            for (name, intervals, pool) in [
+                (
+                    counts[0],
+                    &template.mutator_count,
+                    self.deep_dive_mutators.len(),
+                ),
+                (
+                    counts[1],
+                    &template.warning_count,
+                    self.deep_dive_warnings.len(),
+                ),
+            ] {
+                let limit = pool.min(3);
+                if let Some(i) = intervals
+                    .iter()
+                    .find(|i| i.min < 0 || i.min > i.max || i.max as usize > limit)
+                {
+                    return Err(DataPackError::IntervalOutOfRange(name, i.min, i.max, limit));
+                }
+                check_weights(name, intervals.iter().map(|i| i.weight))?;
+            }
+            for mission in &template.missions {
+                if !self.mission_templates.contains_key(&mission.mission) {
+                    return Err(DataPackError::MissingMissionTemplate(mission.mission));
+                }
+            }
+        }
+        if let Some((key, _)) = self
+            .dna
+            .iter()
+            .find(|(_, d)| d.weight < 0.0 || d.weight.is_nan())
+        {
+            return Err(DataPackError::NegativeDnaWeight(*key));
+        }
+        for (key, template) in &self.mission_templates {
+            if template.secondary_objectives.is_empty() {
+                return Err(DataPackError::EmptyMissionTemplate(
+                    *key,
+                    "secondary objectives",
+                ));
+            }
+            if template.deep_dive_objectives.is_empty() {
+                return Err(DataPackError::EmptyMissionTemplate(
+                    *key,
+                    "Deep Dive objectives",
+                ));
+            }
+            if template.dna.is_empty() {
+                return Err(DataPackError::EmptyMissionTemplate(*key, "DNA"));
+            }
+            if let Some(dna) = template.dna.iter().find(|d| !self.dna.contains_key(d)) {
+                return Err(DataPackError::MissingDna(*dna));
+            }
+            if !template.dna.iter().any(|d| self.dna[d].weight > 0.0) {
+                return Err(DataPackError::EmptyMissionTemplate(
+                    *key,
+                    "DNA with a positive weight",
+                ));
+            }
+        }
+        for template in [&self.normal_template, &self.hard_template] {
+            for mission in &template.missions {
+                let dna: Vec<_> = self.mission_templates[&mission.mission]
+                    .dna
+                    .iter()
+                    .map(|d| &self.dna[d])
+                    .filter(|d| d.weight > 0.0)
+                    .collect();
+                // like the generator, complexities only count when no duration is given
+                if !mission.allowed_durations.is_empty() {
+                    if let Some(duration) = mission
+                        .allowed_durations
+                        .iter()
+                        .find(|duration| dna.iter().all(|d| d.duration != **duration))
+                    {
+                        return Err(DataPackError::NoMatchingDna(
+                            mission.mission,
+                            format!("{duration:?}"),
+                        ));
+                    }
+                } else if let Some(complexity) = mission
+                    .allowed_complexities
+                    .iter()
+                    .find(|complexity| dna.iter().all(|d| d.complexity != **complexity))
+                {
+                    return Err(DataPackError::NoMatchingDna(
+                        mission.mission,
+                        format!("{complexity:?}"),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the pack and turns it into [`GenerationData`].
+    ///
+    /// The tables get the same `'static` lifetime as the compiled-in ones. Loaded packs are kept
+    /// by content, so loading an equal pack again returns the same data instead of allocating it
+    /// again.
+    pub fn load(self) -> Result<&'static GenerationData, DataPackError> {
+        self.validate()?;
+        let mut loaded = LOADED.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, data)) = loaded.iter().find(|(pack, _)| *pack == self) {
+            return Ok(data);
+        }
+        let data = Box::leak(Box::new(self.clone().into_data()));
+        loaded.push((self, data));
+        Ok(data)
+    }
+
+    fn into_data(self) -> GenerationData {
+        GenerationData {
+            biomes: leak(self.biomes),
+            mission_templates: self
+                .mission_templates
+                .into_iter()
+                .map(|(k, t)| {
+                    let item = FMissionTemplateItem {
+                        mission_template: UMissionTemplate {
+                            primary_objective: t.primary_objective,
+                            secondary_objectives: leak(t.secondary_objectives),
+                            deep_dive_objectives: leak(t.deep_dive_objectives),
+                            dna: leak(t.dna),
+                        },
+                        rarity: t.rarity,
+                    };
+                    (k, &*Box::leak(Box::new(item)))
+                })
+                .collect(),
+            dna: self
+                .dna
+                .into_iter()
+                .map(|(k, d)| {
+                    let dna = UMissionDNA {
+                        duration: d.duration,
+                        complexity: d.complexity,
+                        weight: d.weight,
+                        requirement: d.requirement,
+                    };
+                    (k, &*Box::leak(Box::new(dna)))
+                })
+                .collect(),
+            normal_template: self.normal_template.leak(),
+            hard_template: self.hard_template.leak(),
+            deep_dive_settings: Box::leak(Box::new(UDeepDiveSettings {
+                mutators: leak(self.deep_dive_mutators),
+                warnings: leak(self.deep_dive_warnings),
+            })),
+            names_first: leak_names(self.names_first),
+            names_last: leak_names(self.names_last),
+            bans: self.bans,
+        }
+    }
+}
+
+impl DeepDiveTemplate {
+    fn from_template(template: &UDeepDiveTemplate) -> Self {
+        let intervals = |interval: &FRandInterval| {
+            interval
+                .intervals
+                .iter()
+                .map(|i| RandInterval {
+                    weight: i.weight,
+                    min: i.range.min,
+                    max: i.range.max,
+                })
+                .collect()
+        };
+        DeepDiveTemplate {
+            mutator_count: intervals(&template.mutator_count),
+            warning_count: intervals(&template.warning_count),
+            missions: template
+                .missions
+                .iter()
+                .map(|m| DeepDiveMission {
+                    mission: m.mission,
+                    probability: m.probability,
+                    allowed_durations: m.allowed_durations.to_vec(),
+                    allowed_complexities: m.allowed_complexities.to_vec(),
+                    can_only_appear_once: m.can_only_appear_once,
+                    can_only_appear_once_per_deep_dive_set: m
+                        .can_only_appear_once_per_deep_dive_set,
+                })
+                .collect(),
+        }
+    }
+
+    fn leak(self) -> &'static UDeepDiveTemplate {
+        let intervals = |intervals: Vec<RandInterval>| FRandInterval {
+            intervals: leak(
+                intervals
+                    .into_iter()
+                    .map(|i| FRandIntervalItem {
+                        weight: i.weight,
+                        range: FIRandRange {
+                            min: i.min,
+                            max: i.max,
+                        },
+                    })
+                    .collect(),
+            ),
+        };
+        Box::leak(Box::new(UDeepDiveTemplate {
+            mutator_count: intervals(self.mutator_count),
+            warning_count: intervals(self.warning_count),
+            missions: leak(
+                self.missions
+                    .into_iter()
+                    .map(|m| FDeepDiveTemplateItem {
+                        mission: m.mission,
+                        probability: m.probability,
+                        allowed_durations: leak(m.allowed_durations),
+                        allowed_complexities: leak(m.allowed_complexities),
+                        can_only_appear_once: m.can_only_appear_once,
+                        can_only_appear_once_per_deep_dive_set: m
+                            .can_only_appear_once_per_deep_dive_set,
+                    })
+                    .collect(),
+            ),
+        }))
+    }
+}
+
+fn check_weights(
+    name: &'static str,
+    weights: impl Iterator<Item = f32> + Clone,
+) -> Result<(), DataPackError> {
+    if weights.clone().any(|w| w < 0.0 || w.is_nan()) {
+        return Err(DataPackError::NegativeWeight(name));
+    }
+    if !weights.into_iter().any(|w| w > 0.0) {
+        return Err(DataPackError::NoWeight(name));
+    }
+    Ok(())
+}
+
+fn leak<T>(items: Vec<T>) -> &'static [T] {
+    items.leak()
+}
+
+fn leak_names(names: Vec<String>) -> &'static [&'static str] {
+    leak(names.into_iter().map(|n| &*n.leak()).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{gen_deep_dive_pair, gen_deep_dive_pair_with};
+
+    #[test]
+    fn test_round_trip() {
+        let pack = DataPack::from_version(GameVersion::LATEST);
+
+        let json = DataPack::from_json(&serde_json::to_string(&pack).unwrap()).unwrap();
+        let toml = DataPack::from_toml(&toml::to_string(&pack).unwrap()).unwrap();
+        assert_eq!(pack, json);
+        assert_eq!(pack, toml);
+
+        let data = toml.load().unwrap();
+        for seed in [0, 2048, 66099, 0x1ffff] {
+            assert_eq!(
+                gen_deep_dive_pair_with(data, seed),
+                gen_deep_dive_pair(seed)
+            );
+        }
+        assert!(
+            std::ptr::eq(data, json.load().unwrap()),
+            "equal packs are loaded once"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.mission_templates
+            .remove(&EMissionTemplate::MissionType_Elimination);
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::MissingMissionTemplate(
+                EMissionTemplate::MissionType_Elimination
+            ))
+        ));
+
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.biomes.truncate(1);
+        assert!(matches!(
+            pack.load(),
+            Err(DataPackError::NotEnoughBiomes(1))
+        ));
+    }
+
+    #[test]
+    fn test_validate_interval_bounds() {
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.normal_template.mutator_count[0].min = 2;
+        pack.normal_template.mutator_count[0].max = 1;
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::IntervalOutOfRange(
+                "normal_template.mutator_count",
+                2,
+                1,
+                3
+            ))
+        ));
+
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.hard_template.warning_count[0].max = 4;
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::IntervalOutOfRange(
+                "hard_template.warning_count",
+                _,
+                4,
+                3
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_validate_interval_weights() {
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        for interval in &mut pack.hard_template.mutator_count {
+            interval.weight = 0.0;
+        }
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::NoWeight("hard_template.mutator_count"))
+        ));
+
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.normal_template.warning_count[0].weight = -1.0;
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::NegativeWeight(
+                "normal_template.warning_count"
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_validate_dna_weights() {
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        let (key, dna) = pack.dna.iter_mut().next().unwrap();
+        dna.weight = -1.0;
+        let key = *key;
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::NegativeDnaWeight(k)) if k == key
+        ));
+
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        let template = EMissionTemplate::MissionType_Elimination;
+        for dna in pack.mission_templates[&template].dna.clone() {
+            pack.dna.get_mut(&dna).unwrap().weight = 0.0;
+        }
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::EmptyMissionTemplate(t, "DNA with a positive weight")) if t == template
+        ));
+    }
+
+    #[test]
+    fn test_validate_allowed_dna() {
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        let mission = &mut pack.normal_template.missions[0];
+        mission.allowed_durations = vec![EMissionDuration::MD_Duration_Long];
+        let template = mission.mission;
+        for dna in pack.dna.values_mut() {
+            dna.duration = EMissionDuration::MD_Duration_Short;
+        }
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::NoMatchingDna(t, _)) if t == template
+        ));
+
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        let mission = &mut pack.hard_template.missions[0];
+        mission.allowed_durations.clear();
+        mission.allowed_complexities = vec![EMissionComplexity::MD_Complexity_Complex];
+        let template = mission.mission;
+        for dna in pack.dna.values_mut() {
+            dna.complexity = EMissionComplexity::MD_Complexity_Simple;
+        }
+        assert!(matches!(
+            pack.validate(),
+            Err(DataPackError::NoMatchingDna(t, _)) if t == template
+        ));
+    }
+}
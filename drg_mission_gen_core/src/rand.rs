@@ -1,3 +1,63 @@
+//! UE4's `FRandomStream`, extended with the inverse and jump-ahead of its generator.
+//!
+//! The stream is a linear congruential generator `seed * MULTIPLIER + INCREMENT` modulo 2^32 with
+//! full period: every `u32` is visited once per 2^32 steps. That makes each step invertible and
+//! lets any number of steps be taken in O(log n), forwards with [`FRandomStream::advance`] or
+//! backwards with [`FRandomStream::rewind`]. [`FRandomStream::steps_between`] finds how many
+//! steps separate two states, e.g. a mission seed from the stream it was drawn from.
+//!
+//! [`record_draws`] logs every draw made on the current thread, for comparing the order of draws
+//! with the game when porting its logic (see [`crate::first_divergence`]).
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::Location;
+
+use serde::Serialize;
+
+/// Multiplier of [`FRandomStream::next_seed`].
+pub const MULTIPLIER: u32 = 0xbb38435;
+/// Increment of [`FRandomStream::next_seed`].
+pub const INCREMENT: u32 = 0x3619636b;
+/// Multiplicative inverse of [`MULTIPLIER`] modulo 2^32.
+pub const INVERSE_MULTIPLIER: u32 = inverse(MULTIPLIER);
+
+/// Newton iteration for odd `a`, each step doubles the number of correct low bits.
+const fn inverse(a: u32) -> u32 {
+    let mut x = a; // correct to 3 bits for odd a
+    let mut i = 0;
+    while i < 4 {
+        x = x.wrapping_mul(2u32.wrapping_sub(a.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+}
+
+/// Seed `steps` mutations after `seed`.
+pub const fn seed_after(seed: u32, steps: u32) -> u32 {
+    // compose the affine step with itself by squaring: (m, c) applied twice is (m², c·(m + 1))
+    let (mut mult, mut inc) = (MULTIPLIER, INCREMENT);
+    let (mut acc_mult, mut acc_inc) = (1u32, 0u32);
+    let mut steps = steps;
+    while steps > 0 {
+        if steps & 1 == 1 {
+            acc_mult = acc_mult.wrapping_mul(mult);
+            acc_inc = acc_inc.wrapping_mul(mult).wrapping_add(inc);
+        }
+        inc = inc.wrapping_mul(mult.wrapping_add(1));
+        mult = mult.wrapping_mul(mult);
+        steps >>= 1;
+    }
+    seed.wrapping_mul(acc_mult).wrapping_add(acc_inc)
+}
+
+/// Seed `steps` mutations before `seed`.
+pub const fn seed_before(seed: u32, steps: u32) -> u32 {
+    // the period is 2^32, so going back n steps is going forward 2^32 - n
+    seed_after(seed, steps.wrapping_neg())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FRandomStream {
     initial_seed: u32,
     seed: u32,
@@ -11,6 +71,16 @@ impl FRandomStream {
         }
     }
 
+    /// Seed the stream was created with.
+    pub fn initial_seed(&self) -> u32 {
+        self.initial_seed
+    }
+
+    /// Goes back to the initial seed.
+    pub fn reset(&mut self) {
+        self.seed = self.initial_seed
+    }
+
     pub fn seed(&self) -> u32 {
         self.seed
     }
@@ -20,43 +90,271 @@ impl FRandomStream {
     }
 
     pub fn next_seed(&self) -> u32 {
-        self.seed.wrapping_mul(0xbb38435).wrapping_add(0x3619636b)
+        self.seed.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT)
+    }
+
+    /// Seed that [`Self::next_seed`] maps to the current one.
+    pub fn prev_seed(&self) -> u32 {
+        self.seed
+            .wrapping_sub(INCREMENT)
+            .wrapping_mul(INVERSE_MULTIPLIER)
     }
 
+    #[track_caller]
     pub fn mutate(&mut self) {
         self.seed = self.next_seed();
-        //println!("MUTATE SEED {}", self.0);
+        self.record(DrawOp::Mutate);
     }
 
+    /// Undoes one [`Self::mutate`].
+    pub fn unmutate(&mut self) {
+        self.seed = self.prev_seed();
+    }
+
+    /// Same as calling [`Self::mutate`] `steps` times.
+    pub fn advance(&mut self, steps: u32) {
+        self.seed = seed_after(self.seed, steps);
+    }
+
+    /// Same as calling [`Self::unmutate`] `steps` times.
+    pub fn rewind(&mut self, steps: u32) {
+        self.seed = seed_before(self.seed, steps);
+    }
+
+    /// Number of mutations that lead from seed `from` to seed `to`. Always exists since the
+    /// generator visits every seed.
+    pub fn steps_between(from: u32, to: u32) -> u32 {
+        // fixes one bit of the distance at a time: after taking 2^i steps for each set bit below
+        // i, the low i bits match and the step of 2^i decides bit i
+        let (mut mult, mut inc) = (MULTIPLIER, INCREMENT);
+        let mut seed = from;
+        let mut steps = 0;
+        let mut bit = 1u32;
+        while seed != to {
+            if seed & bit != to & bit {
+                seed = seed.wrapping_mul(mult).wrapping_add(inc);
+                steps |= bit;
+            }
+            inc = inc.wrapping_mul(mult.wrapping_add(1));
+            mult = mult.wrapping_mul(mult);
+            bit <<= 1;
+        }
+        steps
+    }
+
+    #[track_caller]
     pub fn get_fraction(&mut self) -> f32 {
-        self.mutate();
+        let fraction = self.next_fraction();
+        self.record(DrawOp::GetFraction { fraction });
+        fraction
+    }
+
+    fn next_fraction(&mut self) -> f32 {
+        self.seed = self.next_seed();
+        self.fraction()
+    }
+
+    /// Fraction of the current seed, which is what the last [`Self::get_fraction`] returned.
+    pub fn fraction(&self) -> f32 {
         f32::from_bits(0x3f800000 | self.seed >> 9) - 1.0
     }
 
+    #[track_caller]
     pub fn rand_helper(&mut self, max: i32) -> i32 {
+        let result = self.next_index(max);
+        self.record(DrawOp::RandHelper { max, result });
+        result
+    }
+
+    fn next_index(&mut self, max: i32) -> i32 {
         if max > 0 {
-            (self.get_fraction() * (max as f32)) as i32
+            (self.next_fraction() * (max as f32)) as i32
         } else {
             0
         }
     }
 
+    #[track_caller]
     pub fn rand_range(&mut self, min: i32, max: i32) -> i32 {
         min + self.rand_helper(max - min + 1)
     }
 
+    #[track_caller]
     pub fn rand_item<'a, T>(&mut self, slice: &'a [T]) -> &'a T {
-        let i = self.rand_helper(slice.len() as i32) as usize;
+        let i = self.next_index(slice.len() as i32) as usize;
+        self.record(DrawOp::RandItem {
+            len: slice.len(),
+            index: i,
+        });
         &slice[i]
     }
 
+    #[track_caller]
     pub fn rand_swap_remove<T>(&mut self, vec: &mut Vec<T>) -> T {
         let i = self.rand_helper(vec.len() as i32) as usize;
         vec.swap_remove(i)
     }
 
+    #[track_caller]
     pub fn rand_remove<T>(&mut self, vec: &mut Vec<T>) -> T {
         let i = self.rand_helper(vec.len() as i32) as usize;
         vec.remove(i)
     }
+
+    /// Logs a draw that was just made if [`record_draws`] is running.
+    #[track_caller]
+    #[inline]
+    fn record(&self, op: DrawOp) {
+        if RECORDING.get() {
+            record_draw(self, op);
+        }
+    }
+}
+
+/// A call on an [`FRandomStream`] logged by [`record_draws`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Draw {
+    /// Initial seed of the stream, which tells apart the dive, mission and helper streams.
+    pub stream: u32,
+    pub op: DrawOp,
+    /// Where the draw was made, e.g. `drg_mission_gen_core/src/lib.rs:816`.
+    pub label: Label,
+    /// Seed of the stream after the draw.
+    pub seed: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum DrawOp {
+    Mutate,
+    GetFraction { fraction: f32 },
+    RandHelper { max: i32, result: i32 },
+    RandItem { len: usize, index: usize },
+}
+
+/// Source location of a draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Label(&'static Location<'static>);
+
+impl Label {
+    pub fn file(&self) -> &'static str {
+        self.0.file()
+    }
+
+    pub fn line(&self) -> u32 {
+        self.0.line()
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file(), self.line())
+    }
+}
+
+impl Serialize for Label {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Display for Draw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op {
+            DrawOp::Mutate => write!(f, "mutate")?,
+            DrawOp::GetFraction { fraction } => write!(f, "get_fraction = {fraction}")?,
+            DrawOp::RandHelper { max, result } => write!(f, "rand_helper({max}) = {result}")?,
+            DrawOp::RandItem { len, index } => write!(f, "rand_item(len {len}) = {index}")?,
+        }
+        write!(
+            f,
+            " -> seed {} (stream {}, {})",
+            self.seed, self.stream, self.label
+        )
+    }
+}
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+    static DRAWS: RefCell<Vec<Draw>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calls `f` and returns the draws it made on this thread, in order. Calls that are built on other
+/// calls are logged once, so `rand_range` is logged as one `rand_helper`.
+pub fn record_draws<T>(f: impl FnOnce() -> T) -> (T, Vec<Draw>) {
+    struct Recording(bool);
+    impl Drop for Recording {
+        fn drop(&mut self) {
+            RECORDING.set(self.0);
+            if !self.0 {
+                DRAWS.with_borrow_mut(Vec::clear);
+            }
+        }
+    }
+
+    let _recording = Recording(RECORDING.replace(true));
+    let start = DRAWS.with_borrow(Vec::len);
+    let result = f();
+    let draws = DRAWS.with_borrow(|draws| draws[start..].to_vec());
+    (result, draws)
+}
+
+#[cold]
+#[track_caller]
+fn record_draw(rand: &FRandomStream, op: DrawOp) {
+    let draw = Draw {
+        stream: rand.initial_seed,
+        op,
+        label: Label(Location::caller()),
+        seed: rand.seed,
+    };
+    DRAWS.with_borrow_mut(|draws| draws.push(draw));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(MULTIPLIER.wrapping_mul(INVERSE_MULTIPLIER), 1);
+        for seed in [0, 1, 216, 0xdeadbeef, u32::MAX] {
+            let mut rand = FRandomStream::new(seed);
+            rand.mutate();
+            assert_eq!(rand.prev_seed(), seed);
+            rand.unmutate();
+            assert_eq!(rand.seed(), seed);
+        }
+    }
+
+    #[test]
+    fn test_jump() {
+        for seed in [0, 216, 0xdeadbeef] {
+            let mut stepped = FRandomStream::new(seed);
+            for steps in 0..300 {
+                let mut jumped = FRandomStream::new(seed);
+                jumped.advance(steps);
+                assert_eq!(jumped.seed(), stepped.seed(), "{seed} + {steps}");
+                assert_eq!(FRandomStream::steps_between(seed, stepped.seed()), steps);
+                jumped.rewind(steps);
+                assert_eq!(jumped.seed(), seed);
+                stepped.mutate();
+            }
+        }
+
+        let far = seed_after(12345, 0x8765_4321);
+        assert_eq!(FRandomStream::steps_between(12345, far), 0x8765_4321);
+        assert_eq!(seed_before(far, 0x8765_4321), 12345);
+        assert_eq!(seed_after(12345, 0), 12345);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rand = FRandomStream::new(42);
+        rand.rand_range(0, 10);
+        rand.rand_range(0, 10);
+        assert_eq!(FRandomStream::steps_between(42, rand.seed()), 2);
+        rand.reset();
+        assert_eq!(rand.seed(), rand.initial_seed());
+    }
 }
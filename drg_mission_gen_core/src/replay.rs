@@ -0,0 +1,157 @@
+//! Replays Deep Dive generation with [`record_draws`] and compares the draws with a trace captured
+//! from the game, to find where a port starts drawing in a different order than the game.
+//!
+//! A game trace is text with one draw per line, ending in the seed of the stream after the draw,
+//! e.g. `MUTATE SEED 1234567` as logged by a hook on `FRandomStream::MutateSeed`. Blank lines and
+//! lines starting with `#` are skipped.
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::rand::{record_draws, Draw};
+use crate::{try_gen_deep_dive_pair_with, GameVersion, GenError, GenerationData, UDeepDive};
+
+/// Records the draws of [`crate::gen_deep_dive_pair`] for `seed`.
+pub fn record_deep_dive_pair_draws(
+    seed: u32,
+) -> Result<((UDeepDive, UDeepDive), Vec<Draw>), GenError> {
+    record_deep_dive_pair_draws_with(GameVersion::LATEST.data(), seed)
+}
+
+/// [`record_deep_dive_pair_draws`] with arbitrary generation data, such as a loaded
+/// [`crate::DataPack`].
+pub fn record_deep_dive_pair_draws_with(
+    data: &GenerationData,
+    seed: u32,
+) -> Result<((UDeepDive, UDeepDive), Vec<Draw>), GenError> {
+    let (pair, draws) = record_draws(|| try_gen_deep_dive_pair_with(data, seed));
+    Ok((pair?, draws))
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("line {line}: expected a seed at the end of {text:?}")]
+pub struct TraceParseError {
+    pub line: usize,
+    pub text: String,
+}
+
+/// A seed from a game trace with the line it was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TracedSeed {
+    pub line: usize,
+    pub seed: u32,
+}
+
+/// Reads the seeds of a game trace. Seeds are decimal, or hexadecimal with a `0x` prefix.
+pub fn parse_game_trace(text: &str) -> Result<Vec<TracedSeed>, TraceParseError> {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, text)| {
+            let word = text
+                .rsplit(|c: char| c.is_whitespace() || c == '=' || c == ':')
+                .next()
+                .unwrap_or_default();
+            let seed = match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => word.parse().ok(),
+            };
+            seed.map(|seed| TracedSeed { line, seed })
+                .ok_or_else(|| TraceParseError {
+                    line,
+                    text: text.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// First point where recorded draws and a game trace disagree.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Divergence {
+    /// Index of the first differing draw, which is also the number of draws that matched.
+    pub index: usize,
+    /// The recorded draw, `None` if the recording ended first.
+    pub recorded: Option<Draw>,
+    /// The seed from the game, `None` if the trace ended first.
+    pub traced: Option<TracedSeed>,
+}
+
+/// Compares the seed after every recorded draw with the trace, in order. Returns `None` when both
+/// have the same seeds.
+pub fn first_divergence(recorded: &[Draw], traced: &[TracedSeed]) -> Option<Divergence> {
+    let index = recorded
+        .iter()
+        .zip(traced)
+        .position(|(draw, traced)| draw.seed != traced.seed)
+        .unwrap_or(recorded.len().min(traced.len()));
+    (index < recorded.len().max(traced.len())).then(|| Divergence {
+        index,
+        recorded: recorded.get(index).copied(),
+        traced: traced.get(index).copied(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_deep_dive_pair;
+    use crate::rand::{DrawOp, FRandomStream};
+
+    #[test]
+    fn test_record_draws() {
+        let (_, draws) = record_draws(|| {
+            let mut rand = FRandomStream::new(216);
+            rand.mutate();
+            rand.rand_range(0, 10);
+            rand.rand_item(&[1, 2, 3]);
+            rand.get_fraction();
+        });
+        let ops: Vec<_> = draws.iter().map(|d| d.op).collect();
+        assert!(matches!(
+            ops[..],
+            [
+                DrawOp::Mutate,
+                DrawOp::RandHelper { max: 11, .. },
+                DrawOp::RandItem { len: 3, .. },
+                DrawOp::GetFraction { .. },
+            ]
+        ));
+        assert!(draws.iter().all(|d| d.stream == 216));
+        assert!(draws.iter().all(|d| d.label.file().ends_with("replay.rs")));
+        for pair in draws.windows(2) {
+            assert_eq!(FRandomStream::steps_between(pair[0].seed, pair[1].seed), 1);
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let (pair, draws) = record_deep_dive_pair_draws(216).unwrap();
+        assert_eq!(pair, gen_deep_dive_pair(216));
+        assert!(!draws.is_empty());
+
+        let trace: String = draws
+            .iter()
+            .map(|d| format!("MUTATE SEED {}\n", d.seed))
+            .collect();
+        let mut traced = parse_game_trace(&format!("# captured\n\n{trace}")).unwrap();
+        assert_eq!(traced[0].line, 3);
+        assert_eq!(first_divergence(&draws, &traced), None);
+
+        traced[5].seed ^= 1;
+        let divergence = first_divergence(&draws, &traced).unwrap();
+        assert_eq!(divergence.index, 5);
+        assert_eq!(divergence.recorded, Some(draws[5]));
+
+        let divergence = first_divergence(&draws, &traced[..3]).unwrap();
+        assert_eq!((divergence.index, divergence.traced), (3, None));
+
+        assert_eq!(
+            parse_game_trace("seed = 0x10\nMUTATE SEED").unwrap_err(),
+            TraceParseError {
+                line: 2,
+                text: "MUTATE SEED".to_string()
+            }
+        );
+    }
+}
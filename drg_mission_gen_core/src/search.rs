@@ -0,0 +1,272 @@
+//! Parallel search over Deep Dive seeds.
+//!
+//! Seeds are handed to the predicate as a [`LazyDeepDivePair`] that only generates what is looked
+//! at, so a predicate checking elite stage 1 first rejects most seeds without generating the rest
+//! of the pair.
+
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use crate::explain::Trace;
+use crate::{
+    try_deep_dive_biomes, DeepDiveKind, DeepDiveStages, EBiome, EMissionTemplate, GameVersion,
+    GenError, GenerationData, UDeepDive, UGeneratedMission,
+};
+
+/// A Deep Dive pair generated on demand. Stages are generated in order the first time something
+/// at or after them is asked for.
+pub struct LazyDeepDivePair<'a> {
+    seed: u32,
+    biomes: (EBiome, EBiome),
+    normal: DeepDiveStages<'a>,
+    elite: DeepDiveStages<'a>,
+    used_missions: Vec<EMissionTemplate>,
+}
+
+impl<'a> LazyDeepDivePair<'a> {
+    /// # Panics
+    ///
+    /// If the data cannot generate the biomes or the codenames, see [`LazyDeepDivePair::try_new`].
+    pub fn new(data: &'a GenerationData, seed: u32) -> Self {
+        Self::try_new(data, seed).unwrap_or_else(|e| panic!("seed {seed}: {e}"))
+    }
+
+    /// [`LazyDeepDivePair::new`] that returns the step that failed instead of panicking.
+    pub fn try_new(data: &'a GenerationData, seed: u32) -> Result<Self, GenError> {
+        let deep_dive_seed = seed & 0x1ffff;
+        let biomes = try_deep_dive_biomes(data, deep_dive_seed, &mut Trace::off())?;
+        Ok(LazyDeepDivePair {
+            seed,
+            biomes,
+            normal: DeepDiveStages::new(
+                data,
+                DeepDiveKind::Normal,
+                deep_dive_seed,
+                biomes.0,
+                false,
+            )?,
+            elite: DeepDiveStages::new(data, DeepDiveKind::Elite, deep_dive_seed, biomes.1, false)?,
+            used_missions: vec![],
+        })
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn biome(&self, kind: DeepDiveKind) -> EBiome {
+        match kind {
+            DeepDiveKind::Normal => self.biomes.0,
+            DeepDiveKind::Elite => self.biomes.1,
+        }
+    }
+
+    /// Codename of the dive, e.g. "Angry Overhang".
+    pub fn name(&self, kind: DeepDiveKind) -> String {
+        match kind {
+            DeepDiveKind::Normal => self.normal.name(),
+            DeepDiveKind::Elite => self.elite.name(),
+        }
+    }
+
+    /// Stage `index` (0 to 2) of a dive. The elite dive avoids missions used by the normal one, so
+    /// its first stage needs all of the normal dive.
+    ///
+    /// # Panics
+    ///
+    /// If the data cannot generate the stage, see [`LazyDeepDivePair::try_stage`].
+    pub fn stage(&mut self, kind: DeepDiveKind, index: usize) -> &UGeneratedMission {
+        let seed = self.seed;
+        self.try_stage(kind, index)
+            .unwrap_or_else(|e| panic!("seed {seed}: {e}"))
+    }
+
+    /// [`LazyDeepDivePair::stage`] that returns the step that failed instead of panicking.
+    pub fn try_stage(
+        &mut self,
+        kind: DeepDiveKind,
+        index: usize,
+    ) -> Result<&UGeneratedMission, GenError> {
+        assert!(index < 3, "Deep Dives have 3 stages");
+        let until = match kind {
+            DeepDiveKind::Normal => index + 1,
+            DeepDiveKind::Elite => 3,
+        };
+        while self.normal.stages.len() < until {
+            self.normal.next_stage(&mut self.used_missions)?;
+        }
+        if kind == DeepDiveKind::Elite {
+            while self.elite.stages.len() <= index {
+                self.elite.next_stage(&mut self.used_missions)?;
+            }
+        }
+        Ok(match kind {
+            DeepDiveKind::Normal => &self.normal.stages[index],
+            DeepDiveKind::Elite => &self.elite.stages[index],
+        })
+    }
+
+    /// Generates whatever is left and returns the pair, same as [`crate::gen_deep_dive_pair`].
+    pub fn finish(mut self) -> (UDeepDive, UDeepDive) {
+        self.stage(DeepDiveKind::Elite, 2);
+        (self.normal.finish(), self.elite.finish())
+    }
+}
+
+/// Returns the seeds in `seeds` whose pair matches `predicate`, in ascending order. Seeds are
+/// checked on all available threads.
+pub fn search_deep_dive_seeds<F>(seeds: Range<u32>, predicate: F) -> Vec<u32>
+where
+    F: Fn(&mut LazyDeepDivePair) -> bool + Sync,
+{
+    search_deep_dive_seeds_with(GameVersion::LATEST.data(), seeds, predicate)
+}
+
+/// [`search_deep_dive_seeds`] with arbitrary generation data, such as a loaded
+/// [`crate::DataPack`].
+///
+/// # Panics
+///
+/// If the data cannot generate a pair, see [`try_search_deep_dive_seeds_with`].
+pub fn search_deep_dive_seeds_with<F>(
+    data: &GenerationData,
+    seeds: Range<u32>,
+    predicate: F,
+) -> Vec<u32>
+where
+    F: Fn(&mut LazyDeepDivePair) -> bool + Sync,
+{
+    try_search_deep_dive_seeds_with(data, seeds, |pair| Ok(predicate(pair)))
+        .unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// [`search_deep_dive_seeds_with`] for data that may fail to generate a pair. The predicate can
+/// use [`LazyDeepDivePair::try_stage`] and return its error. The search stops at the first error
+/// and returns it.
+pub fn try_search_deep_dive_seeds_with<F>(
+    data: &GenerationData,
+    seeds: Range<u32>,
+    predicate: F,
+) -> Result<Vec<u32>, GenError>
+where
+    F: Fn(&mut LazyDeepDivePair) -> Result<bool, GenError> + Sync,
+{
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get) as u32;
+    let chunk = seeds.len().div_ceil(threads as usize).max(1) as u32;
+    let failed = AtomicBool::new(false);
+    let check = |range: Range<u32>| {
+        let mut matches = vec![];
+        for seed in range {
+            if failed.load(Ordering::Relaxed) {
+                break;
+            }
+            let matched = LazyDeepDivePair::try_new(data, seed)
+                .and_then(|mut pair| predicate(&mut pair))
+                .inspect_err(|_| failed.store(true, Ordering::Relaxed))?;
+            if matched {
+                matches.push(seed);
+            }
+        }
+        Ok(matches)
+    };
+    if threads == 1 {
+        return check(seeds);
+    }
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|i| {
+                let start = seeds.start.saturating_add(i * chunk).min(seeds.end);
+                let end = start.saturating_add(chunk).min(seeds.end);
+                scope.spawn(move || check(start..end))
+            })
+            .collect();
+        let mut matches = vec![];
+        for handle in handles {
+            // a panicking predicate panics the search with its own message
+            let chunk = handle
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+            matches.extend(chunk);
+        }
+        Ok(matches)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{gen_deep_dive_pair, DataPack, GenStep, RandInterval};
+
+    #[test]
+    fn test_lazy_pair() {
+        for seed in [0, 2048, 66099, 0x1ffff] {
+            let (normal, elite) = gen_deep_dive_pair(seed);
+            let mut lazy = LazyDeepDivePair::new(GameVersion::LATEST.data(), seed);
+            assert_eq!(lazy.name(DeepDiveKind::Elite), elite.name);
+            assert_eq!(lazy.biome(DeepDiveKind::Normal), normal.biome);
+            assert_eq!(lazy.stage(DeepDiveKind::Elite, 1), &elite.missions[1]);
+            assert_eq!(lazy.stage(DeepDiveKind::Normal, 0), &normal.missions[0]);
+            assert_eq!(lazy.finish(), (normal, elite));
+        }
+    }
+
+    #[test]
+    fn test_search() {
+        let matches = search_deep_dive_seeds(0..4096, |pair| {
+            pair.stage(DeepDiveKind::Elite, 0).template == EMissionTemplate::MissionType_Escort
+                && pair.biome(DeepDiveKind::Elite) == EBiome::BIOME_AzureWeald
+        });
+        let expected: Vec<_> = (0..4096)
+            .filter(|&seed| {
+                let (_, elite) = gen_deep_dive_pair(seed);
+                elite.missions[0].template == EMissionTemplate::MissionType_Escort
+                    && elite.biome == EBiome::BIOME_AzureWeald
+            })
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn test_try_search() {
+        // every stage of the normal dive gets a mutator, but all of them are banned
+        let mut pack = DataPack::from_version(GameVersion::LATEST);
+        pack.normal_template.mutator_count = vec![RandInterval {
+            weight: 1.0,
+            min: 3,
+            max: 3,
+        }];
+        let primaries: Vec<_> = pack
+            .mission_templates
+            .values()
+            .map(|t| t.primary_objective)
+            .collect();
+        for mutator in &pack.deep_dive_mutators {
+            pack.bans
+                .mutator_objectives
+                .insert(*mutator, primaries.clone());
+        }
+        let data = pack.load().unwrap();
+        let expected = GenError {
+            kind: DeepDiveKind::Normal,
+            stage: Some(0),
+            step: GenStep::Mutator,
+        };
+
+        let mut pair = LazyDeepDivePair::try_new(data, 216).unwrap();
+        assert_eq!(
+            pair.try_stage(DeepDiveKind::Elite, 0),
+            Err(expected.clone())
+        );
+        assert_eq!(
+            try_search_deep_dive_seeds_with(data, 0..4096, |pair| {
+                Ok(pair.try_stage(DeepDiveKind::Normal, 0)?.template
+                    == EMissionTemplate::MissionType_Escort)
+            }),
+            Err(expected)
+        );
+    }
+}
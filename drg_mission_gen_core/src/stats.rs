@@ -0,0 +1,339 @@
+//! Exact odds of Deep Dive features.
+//!
+//! Every one of the [`DEEP_DIVE_SEED_COUNT`] seeds is equally likely, so the exact probability of
+//! anything is the share of seeds it holds for. The seed space is enumerated once into a
+//! [`DeepDiveTable`] and every query counts over it.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use strum::VariantArray;
+
+use crate::table::{write_table, TableLayout};
+use crate::{
+    DeepDiveKind, DeepDiveTable, EBiome, EDreadnought, EMissionDNA, EMissionMutator,
+    EMissionTemplate, EMissionWarning, EObjective, GameVersion, TableDive, TableStage,
+    DEEP_DIVE_SEED_COUNT,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, VariantArray)]
+pub enum Feature {
+    Biome,
+    MissionTemplate,
+    Dna,
+    SecondaryObjective,
+    Mutator,
+    Warning,
+    /// Targets of Elimination primaries and Dreadnought secondaries.
+    Dreadnought,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FeatureValue {
+    Biome(EBiome),
+    MissionTemplate(EMissionTemplate),
+    Dna(EMissionDNA),
+    SecondaryObjective(EObjective),
+    /// `None` for a stage without a mutator.
+    Mutator(Option<EMissionMutator>),
+    /// `None` for a stage without a warning.
+    Warning(Option<EMissionWarning>),
+    Dreadnought(EDreadnought),
+}
+
+/// Which stages a feature is looked at in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scope {
+    /// Stage 0 to 2 of one dive.
+    Stage(DeepDiveKind, usize),
+    /// Any stage of one dive.
+    Dive(DeepDiveKind),
+    /// Any stage of either dive.
+    Pair,
+}
+
+impl Scope {
+    /// Every stage, every dive and the pair.
+    pub fn all() -> Vec<Scope> {
+        let mut scopes = vec![];
+        for &kind in DeepDiveKind::VARIANTS {
+            scopes.extend((0..3).map(|i| Scope::Stage(kind, i)));
+            scopes.push(Scope::Dive(kind));
+        }
+        scopes.push(Scope::Pair);
+        scopes
+    }
+
+    fn stages<'d>(
+        self,
+        normal: &'d TableDive,
+        elite: &'d TableDive,
+    ) -> Vec<(&'d TableDive, &'d TableStage)> {
+        let dive = |kind| match kind {
+            DeepDiveKind::Normal => normal,
+            DeepDiveKind::Elite => elite,
+        };
+        match self {
+            Scope::Stage(kind, i) => vec![(dive(kind), &dive(kind).stages[i])],
+            Scope::Dive(kind) => dive(kind).stages.iter().map(|s| (dive(kind), s)).collect(),
+            Scope::Pair => [normal, elite]
+                .into_iter()
+                .flat_map(|d| d.stages.iter().map(move |s| (d, s)))
+                .collect(),
+        }
+    }
+}
+
+/// `count` out of `seeds` seeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Probability {
+    pub count: u32,
+    pub seeds: u32,
+}
+
+impl Probability {
+    pub fn as_f64(self) -> f64 {
+        self.count as f64 / self.seeds as f64
+    }
+}
+
+/// Number of seeds each value appears for. Values of a single stage are exclusive except for
+/// dreadnoughts, but a value counts once per seed if it appears in several stages of the scope,
+/// so counts of a wider scope add up to more than `seeds`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    pub seeds: u32,
+    pub counts: BTreeMap<FeatureValue, u32>,
+}
+
+impl Distribution {
+    pub fn probability(&self, value: FeatureValue) -> Probability {
+        Probability {
+            count: self.counts.get(&value).copied().unwrap_or_default(),
+            seeds: self.seeds,
+        }
+    }
+}
+
+/// Number of seeds each pair of values appears together for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JointDistribution {
+    pub seeds: u32,
+    pub counts: BTreeMap<(FeatureValue, FeatureValue), u32>,
+}
+
+impl JointDistribution {
+    pub fn probability(&self, a: FeatureValue, b: FeatureValue) -> Probability {
+        Probability {
+            count: self.counts.get(&(a, b)).copied().unwrap_or_default(),
+            seeds: self.seeds,
+        }
+    }
+}
+
+pub struct DeepDiveStats<'a> {
+    source: Source<'a>,
+}
+
+enum Source<'a> {
+    /// A generated table with its checked layout.
+    Owned(Vec<u8>, TableLayout),
+    Borrowed(DeepDiveTable<'a>),
+}
+
+impl DeepDiveStats<'static> {
+    /// Enumerates every seed of `version` into a table owned by the stats.
+    pub fn generate(version: GameVersion) -> Self {
+        Self::generate_seeds(version, DEEP_DIVE_SEED_COUNT)
+    }
+
+    fn generate_seeds(version: GameVersion, seeds: u32) -> Self {
+        let mut bytes = vec![];
+        write_table(version, seeds, &mut bytes).expect("writing to a Vec");
+        let layout = TableLayout::check(&bytes).expect("freshly written table");
+        DeepDiveStats {
+            source: Source::Owned(bytes, layout),
+        }
+    }
+}
+
+impl<'a> DeepDiveStats<'a> {
+    /// Counts over an existing table instead of generating one.
+    pub fn new(table: DeepDiveTable<'a>) -> Self {
+        DeepDiveStats {
+            source: Source::Borrowed(table),
+        }
+    }
+
+    fn table(&self) -> DeepDiveTable<'_> {
+        match &self.source {
+            Source::Owned(bytes, layout) => layout.table(bytes),
+            Source::Borrowed(table) => *table,
+        }
+    }
+
+    pub fn seeds(&self) -> u32 {
+        self.table().seed_count()
+    }
+
+    /// Exact probability of `predicate` holding for a pair.
+    pub fn probability(&self, predicate: impl Fn(&TableDive, &TableDive) -> bool) -> Probability {
+        let count = self
+            .table()
+            .iter()
+            .filter(|(_, (normal, elite))| predicate(normal, elite))
+            .count();
+        Probability {
+            count: count as u32,
+            seeds: self.seeds(),
+        }
+    }
+
+    pub fn marginal(&self, scope: Scope, feature: Feature) -> Distribution {
+        let mut counts = BTreeMap::new();
+        for (_, (normal, elite)) in self.table().iter() {
+            for value in values(scope, feature, &normal, &elite) {
+                *counts.entry(value).or_default() += 1;
+            }
+        }
+        Distribution {
+            seeds: self.seeds(),
+            counts,
+        }
+    }
+
+    /// The marginal distribution of every feature in every scope, see [`Scope::all`].
+    pub fn marginals(&self) -> Vec<(Scope, Feature, Distribution)> {
+        let mut all = vec![];
+        for scope in Scope::all() {
+            for &feature in Feature::VARIANTS {
+                all.push((scope, feature, self.marginal(scope, feature)));
+            }
+        }
+        all
+    }
+
+    pub fn joint(&self, a: (Scope, Feature), b: (Scope, Feature)) -> JointDistribution {
+        let mut counts = BTreeMap::new();
+        for (_, (normal, elite)) in self.table().iter() {
+            let b = values(b.0, b.1, &normal, &elite);
+            for a in values(a.0, a.1, &normal, &elite) {
+                for b in &b {
+                    *counts.entry((a, *b)).or_default() += 1;
+                }
+            }
+        }
+        JointDistribution {
+            seeds: self.seeds(),
+            counts,
+        }
+    }
+}
+
+/// Distinct values of `feature` in the stages of `scope`.
+fn values(
+    scope: Scope,
+    feature: Feature,
+    normal: &TableDive,
+    elite: &TableDive,
+) -> BTreeSet<FeatureValue> {
+    let mut values = BTreeSet::new();
+    for (dive, stage) in scope.stages(normal, elite) {
+        match feature {
+            Feature::Biome => {
+                values.insert(FeatureValue::Biome(dive.biome));
+            }
+            Feature::MissionTemplate => {
+                values.insert(FeatureValue::MissionTemplate(stage.template));
+            }
+            Feature::Dna => {
+                values.insert(FeatureValue::Dna(stage.dna));
+            }
+            Feature::SecondaryObjective => {
+                values.insert(FeatureValue::SecondaryObjective(stage.secondary_objective));
+            }
+            Feature::Mutator => {
+                values.insert(FeatureValue::Mutator(stage.mutator));
+            }
+            Feature::Warning => {
+                values.insert(FeatureValue::Warning(stage.warning));
+            }
+            Feature::Dreadnought => values.extend(
+                stage
+                    .primary_dreadnoughts
+                    .iter()
+                    .chain(&stage.secondary_dreadnoughts)
+                    .map(|d| FeatureValue::Dreadnought(*d)),
+            ),
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_deep_dive_pair;
+
+    #[test]
+    fn test_stats() {
+        let stats = DeepDiveStats::generate_seeds(GameVersion::LATEST, 1000);
+        let pairs: Vec<_> = (0..1000).map(gen_deep_dive_pair).collect();
+        let count = |f: &dyn Fn(&(crate::UDeepDive, crate::UDeepDive)) -> bool| {
+            pairs.iter().filter(|p| f(p)).count() as u32
+        };
+
+        let facility = EMissionTemplate::MissionType_Facility;
+        let elite_facility =
+            count(&|(_, elite)| elite.missions.iter().any(|m| m.template == facility));
+        assert_eq!(
+            stats
+                .marginal(Scope::Dive(DeepDiveKind::Elite), Feature::MissionTemplate)
+                .probability(FeatureValue::MissionTemplate(facility)),
+            Probability {
+                count: elite_facility,
+                seeds: 1000
+            }
+        );
+        assert_eq!(
+            stats
+                .probability(|_, elite| elite.stages.iter().any(|s| s.template == facility))
+                .count,
+            elite_facility
+        );
+
+        let low_gravity = Some(EMissionMutator::MMUT_LowGravity);
+        let any_low_gravity = count(&|(normal, elite)| {
+            normal
+                .missions
+                .iter()
+                .chain(&elite.missions)
+                .any(|m| m.mutators.first().copied() == low_gravity)
+        });
+        let mutators = stats.marginal(Scope::Pair, Feature::Mutator);
+        assert_eq!(
+            mutators
+                .probability(FeatureValue::Mutator(low_gravity))
+                .count,
+            any_low_gravity
+        );
+
+        // every stage has exactly one mission template
+        let stage = stats.marginal(
+            Scope::Stage(DeepDiveKind::Normal, 1),
+            Feature::MissionTemplate,
+        );
+        assert_eq!(stage.counts.values().sum::<u32>(), 1000);
+
+        let joint = stats.joint(
+            (Scope::Dive(DeepDiveKind::Normal), Feature::Biome),
+            (Scope::Dive(DeepDiveKind::Elite), Feature::Biome),
+        );
+        assert_eq!(joint.counts.values().sum::<u32>(), 1000);
+        let biome = FeatureValue::Biome(EBiome::BIOME_AzureWeald);
+        assert_eq!(
+            joint.probability(biome, biome).count,
+            0,
+            "biomes are drawn without replacement"
+        );
+    }
+}
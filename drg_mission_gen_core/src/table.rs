@@ -0,0 +1,552 @@
+//! Compact binary table of every Deep Dive pair, so lookups and queries don't have to generate
+//! anything.
+//!
+//! Layout, all integers little endian:
+//!
+//! | bytes                   | content                                                        |
+//! |-------------------------|----------------------------------------------------------------|
+//! | 8                       | magic `DRGDDTAB`                                               |
+//! | 4                       | format version                                                 |
+//! | 4                       | [`GameVersion`] index                                          |
+//! | 4                       | seed count `n`, seeds are `0..n`                               |
+//! | 4                       | posting count `p`                                              |
+//! | `n` * [`PAIR_SIZE`]     | normal then elite dive of every seed, see [`encode_dive`]      |
+//! | directory               | `(start, len)` u32 pairs into the postings, one per index key  |
+//! | `p` * 4                 | postings: ascending seeds of each index key                    |
+//!
+//! Index keys are every biome, then every mission template, then every mutator, each once per
+//! [`DeepDiveKind`]. Mission seeds are not stored.
+
+use std::io::{self, Write};
+
+use strum::VariantArray;
+use thiserror::Error;
+
+use crate::{
+    gen_deep_dive_pair_for, DeepDiveKind, EBiome, EDreadnought, EMissionDNA, EMissionMutator,
+    EMissionTemplate, EMissionWarning, EObjective, GameVersion, ObjectiveInstance, UDeepDive,
+    UGeneratedMission, DEEP_DIVE_SEED_COUNT,
+};
+
+const MAGIC: &[u8; 8] = b"DRGDDTAB";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 24;
+const STAGE_SIZE: usize = 6;
+const DIVE_SIZE: usize = 5 + 3 * STAGE_SIZE;
+/// Bytes per seed.
+pub const PAIR_SIZE: usize = 2 * DIVE_SIZE;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TableError {
+    #[error("not a Deep Dive table")]
+    BadMagic,
+    #[error("unsupported table format version {0}")]
+    UnsupportedFormat(u32),
+    #[error("unknown game version {0}")]
+    UnknownGameVersion(u32),
+    #[error("table is {actual} bytes but its header describes {expected} bytes")]
+    WrongSize { expected: usize, actual: usize },
+    #[error("corrupted record for seed {0}")]
+    CorruptedRecord(u32),
+    #[error("corrupted index")]
+    CorruptedIndex,
+}
+
+/// Everything shown for one stage of a Deep Dive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableStage {
+    pub template: EMissionTemplate,
+    pub dna: EMissionDNA,
+    pub secondary_objective: EObjective,
+    pub mutator: Option<EMissionMutator>,
+    pub warning: Option<EMissionWarning>,
+    /// Targets of an Elimination primary objective.
+    pub primary_dreadnoughts: Vec<EDreadnought>,
+    /// Target of a Dreadnought secondary objective.
+    pub secondary_dreadnoughts: Vec<EDreadnought>,
+}
+
+impl From<&UGeneratedMission> for TableStage {
+    fn from(mission: &UGeneratedMission) -> Self {
+        let targets = |objective: Option<&ObjectiveInstance>| match objective {
+            Some(ObjectiveInstance::Elimination { targets, .. }) => targets.clone(),
+            _ => vec![],
+        };
+        TableStage {
+            template: mission.template,
+            dna: mission.dna,
+            secondary_objective: mission.secondary_objectives[0].objective(),
+            mutator: mission.mutators.first().copied(),
+            warning: mission.warnings.first().copied(),
+            primary_dreadnoughts: targets(Some(&mission.primary_objective)),
+            secondary_dreadnoughts: targets(mission.secondary_objectives.first()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableDive {
+    pub name: String,
+    pub biome: EBiome,
+    pub stages: [TableStage; 3],
+}
+
+impl TableDive {
+    fn new(dive: &UDeepDive) -> Self {
+        TableDive {
+            name: dive.name.clone(),
+            biome: dive.biome,
+            stages: [0, 1, 2].map(|i| TableStage::from(&dive.missions[i])),
+        }
+    }
+}
+
+/// Read-only view of a table, e.g. of a memory mapped file.
+#[derive(Debug, Clone, Copy)]
+pub struct DeepDiveTable<'a> {
+    version: GameVersion,
+    seed_count: u32,
+    records: &'a [u8],
+    directory: &'a [u8],
+    postings: &'a [u8],
+}
+
+/// Where the parts of a table are, read from its header. Kept next to owned bytes so the header
+/// is only parsed once.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TableLayout {
+    version: GameVersion,
+    seed_count: u32,
+    records_end: usize,
+    directory_end: usize,
+}
+
+impl TableLayout {
+    /// Reads the header and checks every record, see [`DeepDiveTable::from_bytes`].
+    pub(crate) fn check(bytes: &[u8]) -> Result<Self, TableError> {
+        let layout = Self::read(bytes)?;
+        let table = layout.table(bytes);
+        let data = table.version.data();
+        for (seed, pair) in table.records.chunks_exact(PAIR_SIZE).enumerate() {
+            if !pair
+                .chunks_exact(DIVE_SIZE)
+                .all(|dive| is_valid_dive(dive, data.names_first.len(), data.names_last.len()))
+            {
+                return Err(TableError::CorruptedRecord(seed as u32));
+            }
+        }
+        for key in 0..index_key_count() {
+            let (start, len) = table.directory_entry(key);
+            if start + len > table.postings.len() / 4
+                || !table.postings(key).all(|seed| seed < table.seed_count)
+            {
+                return Err(TableError::CorruptedIndex);
+            }
+        }
+        Ok(layout)
+    }
+
+    fn read(bytes: &[u8]) -> Result<Self, TableError> {
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+            return Err(TableError::BadMagic);
+        }
+        let format = read_u32(bytes, 8);
+        if format != FORMAT_VERSION {
+            return Err(TableError::UnsupportedFormat(format));
+        }
+        let version = read_u32(bytes, 12);
+        let version = *GameVersion::VARIANTS
+            .get(version as usize)
+            .ok_or(TableError::UnknownGameVersion(version))?;
+        let seed_count = read_u32(bytes, 16);
+        let posting_count = read_u32(bytes, 20) as usize;
+
+        let records_end = HEADER_SIZE + seed_count as usize * PAIR_SIZE;
+        let directory_end = records_end + index_key_count() * 8;
+        let expected = directory_end + posting_count * 4;
+        if bytes.len() != expected {
+            return Err(TableError::WrongSize {
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        Ok(TableLayout {
+            version,
+            seed_count,
+            records_end,
+            directory_end,
+        })
+    }
+
+    /// View of the bytes the layout was read from.
+    pub(crate) fn table(self, bytes: &[u8]) -> DeepDiveTable<'_> {
+        DeepDiveTable {
+            version: self.version,
+            seed_count: self.seed_count,
+            records: &bytes[HEADER_SIZE..self.records_end],
+            directory: &bytes[self.records_end..self.directory_end],
+            postings: &bytes[self.directory_end..],
+        }
+    }
+}
+
+impl<'a> DeepDiveTable<'a> {
+    /// Checks the header and every record, so reading the table afterwards can't fail.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, TableError> {
+        Ok(TableLayout::check(bytes)?.table(bytes))
+    }
+
+    /// Generates every Deep Dive pair of `version` and writes the table.
+    pub fn write(version: GameVersion, out: &mut impl Write) -> io::Result<()> {
+        write_table(version, DEEP_DIVE_SEED_COUNT, out)
+    }
+
+    pub fn version(&self) -> GameVersion {
+        self.version
+    }
+
+    /// Seeds in the table are `0..seed_count()`.
+    pub fn seed_count(&self) -> u32 {
+        self.seed_count
+    }
+
+    /// The normal and elite dive of `seed`, `None` if the seed is not in the table. Only the lower
+    /// 17 bits of a seed are used, same as [`crate::gen_deep_dive_pair`].
+    pub fn get(&self, seed: u32) -> Option<(TableDive, TableDive)> {
+        let seed = seed & 0x1ffff;
+        if seed >= self.seed_count {
+            return None;
+        }
+        let pair = &self.records[seed as usize * PAIR_SIZE..][..PAIR_SIZE];
+        Some((
+            self.decode_dive(&pair[..DIVE_SIZE]),
+            self.decode_dive(&pair[DIVE_SIZE..]),
+        ))
+    }
+
+    /// Every seed with its normal and elite dive.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, (TableDive, TableDive))> + '_ {
+        (0..self.seed_count).map(|seed| (seed, self.get(seed).unwrap()))
+    }
+
+    /// Seeds whose `kind` dive is in `biome`.
+    pub fn seeds_with_biome(&self, kind: DeepDiveKind, biome: EBiome) -> Postings<'a> {
+        self.postings(index_key(0, EBiome::VARIANTS, kind, biome))
+    }
+
+    /// Seeds whose `kind` dive has a stage with `template`.
+    pub fn seeds_with_template(
+        &self,
+        kind: DeepDiveKind,
+        template: EMissionTemplate,
+    ) -> Postings<'a> {
+        let base = 2 * EBiome::VARIANTS.len();
+        self.postings(index_key(base, EMissionTemplate::VARIANTS, kind, template))
+    }
+
+    /// Seeds whose `kind` dive has a stage with `mutator`.
+    pub fn seeds_with_mutator(&self, kind: DeepDiveKind, mutator: EMissionMutator) -> Postings<'a> {
+        let base = 2 * (EBiome::VARIANTS.len() + EMissionTemplate::VARIANTS.len());
+        self.postings(index_key(base, EMissionMutator::VARIANTS, kind, mutator))
+    }
+
+    fn directory_entry(&self, key: usize) -> (usize, usize) {
+        (
+            read_u32(self.directory, key * 8) as usize,
+            read_u32(self.directory, key * 8 + 4) as usize,
+        )
+    }
+
+    fn postings(&self, key: usize) -> Postings<'a> {
+        let (start, len) = self.directory_entry(key);
+        Postings(self.postings[start * 4..][..len * 4].chunks_exact(4))
+    }
+
+    fn decode_dive(&self, dive: &[u8]) -> TableDive {
+        let data = self.version.data();
+        let first = u16::from_le_bytes([dive[0], dive[1]]) as usize;
+        let last = u16::from_le_bytes([dive[2], dive[3]]) as usize;
+        let stage = |i: usize| decode_stage(&dive[5 + i * STAGE_SIZE..][..STAGE_SIZE]);
+        TableDive {
+            name: format!("{} {}", data.names_first[first], data.names_last[last]),
+            biome: EBiome::VARIANTS[dive[4] as usize],
+            stages: [stage(0), stage(1), stage(2)],
+        }
+    }
+}
+
+/// Ascending seeds of one index key.
+#[derive(Debug, Clone)]
+pub struct Postings<'a>(std::slice::ChunksExact<'a, u8>);
+
+impl Iterator for Postings<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0
+            .next()
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Postings<'_> {}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn index_of<T: PartialEq>(variants: &[T], value: T) -> usize {
+    variants.iter().position(|v| *v == value).unwrap()
+}
+
+fn index_key<T: PartialEq>(base: usize, variants: &[T], kind: DeepDiveKind, value: T) -> usize {
+    base + index_of(DeepDiveKind::VARIANTS, kind) * variants.len() + index_of(variants, value)
+}
+
+fn index_key_count() -> usize {
+    2 * (EBiome::VARIANTS.len()
+        + EMissionTemplate::VARIANTS.len()
+        + EMissionMutator::VARIANTS.len())
+}
+
+/// Variant index plus one, 0 for none.
+fn encode_option<T: PartialEq>(variants: &[T], value: Option<T>) -> u8 {
+    value.map_or(0, |v| index_of(variants, v) as u8 + 1)
+}
+
+fn decode_option<T: Copy>(variants: &[T], byte: u8) -> Option<T> {
+    byte.checked_sub(1).map(|i| variants[i as usize])
+}
+
+/// Two bits per target, primary objective targets in bits 0 to 3 and the secondary objective
+/// target in bits 4 and 5.
+fn encode_dreadnoughts(stage: &TableStage) -> u8 {
+    let bits = |targets: &[EDreadnought]| {
+        targets.iter().enumerate().fold(0, |bits, (i, t)| {
+            bits | encode_option(EDreadnought::VARIANTS, Some(*t)) << (2 * i)
+        })
+    };
+    bits(&stage.primary_dreadnoughts) | bits(&stage.secondary_dreadnoughts) << 4
+}
+
+fn decode_dreadnoughts(byte: u8, shift: u8, count: u8) -> Vec<EDreadnought> {
+    (0..count)
+        .map_while(|i| decode_option(EDreadnought::VARIANTS, byte >> (shift + 2 * i) & 0b11))
+        .collect()
+}
+
+/// Codename word indexes as u16, the biome, then [`STAGE_SIZE`] bytes per stage: template, DNA,
+/// secondary objective, mutator, warning and dreadnoughts.
+fn encode_dive(dive: &TableDive, first: usize, last: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(DIVE_SIZE);
+    bytes.extend((first as u16).to_le_bytes());
+    bytes.extend((last as u16).to_le_bytes());
+    bytes.push(index_of(EBiome::VARIANTS, dive.biome) as u8);
+    for stage in &dive.stages {
+        bytes.extend([
+            index_of(EMissionTemplate::VARIANTS, stage.template) as u8,
+            index_of(EMissionDNA::VARIANTS, stage.dna) as u8,
+            index_of(EObjective::VARIANTS, stage.secondary_objective) as u8,
+            encode_option(EMissionMutator::VARIANTS, stage.mutator),
+            encode_option(EMissionWarning::VARIANTS, stage.warning),
+            encode_dreadnoughts(stage),
+        ]);
+    }
+    bytes
+}
+
+fn decode_stage(stage: &[u8]) -> TableStage {
+    TableStage {
+        template: EMissionTemplate::VARIANTS[stage[0] as usize],
+        dna: EMissionDNA::VARIANTS[stage[1] as usize],
+        secondary_objective: EObjective::VARIANTS[stage[2] as usize],
+        mutator: decode_option(EMissionMutator::VARIANTS, stage[3]),
+        warning: decode_option(EMissionWarning::VARIANTS, stage[4]),
+        primary_dreadnoughts: decode_dreadnoughts(stage[5], 0, 2),
+        secondary_dreadnoughts: decode_dreadnoughts(stage[5], 4, 1),
+    }
+}
+
+fn is_valid_dive(dive: &[u8], names_first: usize, names_last: usize) -> bool {
+    let option = |byte: u8, len: usize| (byte as usize) <= len;
+    (u16::from_le_bytes([dive[0], dive[1]]) as usize) < names_first
+        && (u16::from_le_bytes([dive[2], dive[3]]) as usize) < names_last
+        && (dive[4] as usize) < EBiome::VARIANTS.len()
+        && dive[5..].chunks_exact(STAGE_SIZE).all(|s| {
+            (s[0] as usize) < EMissionTemplate::VARIANTS.len()
+                && (s[1] as usize) < EMissionDNA::VARIANTS.len()
+                && (s[2] as usize) < EObjective::VARIANTS.len()
+                && option(s[3], EMissionMutator::VARIANTS.len())
+                && option(s[4], EMissionWarning::VARIANTS.len())
+                && (0..3).all(|i| option(s[5] >> (2 * i) & 0b11, EDreadnought::VARIANTS.len()))
+        })
+}
+
+pub(crate) fn write_table(
+    version: GameVersion,
+    seed_count: u32,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let data = version.data();
+    // words can contain spaces, e.g. "Leaf Lover's", any pair spelling the name will do
+    let word_indexes = |name: &str| {
+        data.names_first
+            .iter()
+            .enumerate()
+            .find_map(|(i, first)| {
+                let last = name.strip_prefix(first)?.strip_prefix(' ')?;
+                Some((i, data.names_last.iter().position(|l| *l == last)?))
+            })
+            .unwrap()
+    };
+
+    let mut records = Vec::with_capacity(seed_count as usize * PAIR_SIZE);
+    let mut index = vec![vec![]; index_key_count()];
+    for seed in 0..seed_count {
+        let (normal, elite) = gen_deep_dive_pair_for(version, seed);
+        for (kind, dive) in [(DeepDiveKind::Normal, normal), (DeepDiveKind::Elite, elite)] {
+            let (first, last) = word_indexes(&dive.name);
+            let dive = TableDive::new(&dive);
+            records.extend(encode_dive(&dive, first, last));
+
+            let mut keys = vec![index_key(0, EBiome::VARIANTS, kind, dive.biome)];
+            for stage in &dive.stages {
+                let base = 2 * EBiome::VARIANTS.len();
+                keys.push(index_key(
+                    base,
+                    EMissionTemplate::VARIANTS,
+                    kind,
+                    stage.template,
+                ));
+                if let Some(mutator) = stage.mutator {
+                    let base = base + 2 * EMissionTemplate::VARIANTS.len();
+                    keys.push(index_key(base, EMissionMutator::VARIANTS, kind, mutator));
+                }
+            }
+            for key in keys {
+                let seeds: &mut Vec<u32> = &mut index[key];
+                if seeds.last() != Some(&seed) {
+                    seeds.push(seed);
+                }
+            }
+        }
+    }
+
+    let posting_count: usize = index.iter().map(Vec::len).sum();
+    out.write_all(MAGIC)?;
+    out.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    out.write_all(&(index_of(GameVersion::VARIANTS, version) as u32).to_le_bytes())?;
+    out.write_all(&seed_count.to_le_bytes())?;
+    out.write_all(&(posting_count as u32).to_le_bytes())?;
+    out.write_all(&records)?;
+    let mut start = 0;
+    for seeds in &index {
+        out.write_all(&(start as u32).to_le_bytes())?;
+        out.write_all(&(seeds.len() as u32).to_le_bytes())?;
+        start += seeds.len();
+    }
+    for seed in index.iter().flatten() {
+        out.write_all(&seed.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_deep_dive_pair;
+
+    fn table_bytes() -> Vec<u8> {
+        let mut bytes = vec![];
+        write_table(GameVersion::LATEST, 512, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes = table_bytes();
+        let table = DeepDiveTable::from_bytes(&bytes).unwrap();
+        assert_eq!(table.seed_count(), 512);
+        for seed in 0..512 {
+            let (normal, elite) = gen_deep_dive_pair(seed);
+            assert_eq!(
+                table.get(seed),
+                Some((TableDive::new(&normal), TableDive::new(&elite)))
+            );
+        }
+        assert_eq!(table.get(512), None);
+    }
+
+    #[test]
+    fn test_indices() {
+        let bytes = table_bytes();
+        let table = DeepDiveTable::from_bytes(&bytes).unwrap();
+        let dive = |seed, kind| {
+            let (normal, elite) = table.get(seed).unwrap();
+            match kind {
+                DeepDiveKind::Normal => normal,
+                DeepDiveKind::Elite => elite,
+            }
+        };
+        for &kind in DeepDiveKind::VARIANTS {
+            for &biome in EBiome::VARIANTS {
+                let expected: Vec<_> = (0..512).filter(|s| dive(*s, kind).biome == biome).collect();
+                assert_eq!(
+                    table.seeds_with_biome(kind, biome).collect::<Vec<_>>(),
+                    expected
+                );
+            }
+            for &template in EMissionTemplate::VARIANTS {
+                let expected: Vec<_> = (0..512)
+                    .filter(|s| {
+                        dive(*s, kind)
+                            .stages
+                            .iter()
+                            .any(|st| st.template == template)
+                    })
+                    .collect();
+                let found = table.seeds_with_template(kind, template);
+                assert_eq!(found.len(), expected.len());
+                assert_eq!(found.collect::<Vec<_>>(), expected);
+            }
+            for &mutator in EMissionMutator::VARIANTS {
+                let expected: Vec<_> = (0..512)
+                    .filter(|s| {
+                        dive(*s, kind)
+                            .stages
+                            .iter()
+                            .any(|st| st.mutator == Some(mutator))
+                    })
+                    .collect();
+                let found: Vec<_> = table.seeds_with_mutator(kind, mutator).collect();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_corrupted() {
+        let mut bytes = table_bytes();
+        assert_eq!(
+            DeepDiveTable::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            TableError::WrongSize {
+                expected: bytes.len(),
+                actual: bytes.len() - 1
+            }
+        );
+        bytes[HEADER_SIZE + 3 * PAIR_SIZE + 4] = 0xff; // normal biome of seed 3
+        assert_eq!(
+            DeepDiveTable::from_bytes(&bytes).unwrap_err(),
+            TableError::CorruptedRecord(3)
+        );
+        bytes[0] = b'X';
+        assert_eq!(
+            DeepDiveTable::from_bytes(&bytes).unwrap_err(),
+            TableError::BadMagic
+        );
+    }
+}
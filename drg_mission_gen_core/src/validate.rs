@@ -0,0 +1,444 @@
+//! Rule checks for Deep Dives that did not come from this generator, e.g. from community
+//! spreadsheets.
+//!
+//! Rules are read from the [`GenerationData`], so a dive can be checked against a data pack as well
+//! as a built-in version. Rules that need the other dive of the pair, like missions that may only
+//! appear once per pair, are not checked.
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    try_search_deep_dive_seeds_with, DeepDiveKind, EBiome, EMissionComplexity, EMissionDNA,
+    EMissionDuration, EMissionMutator, EMissionTemplate, EMissionWarning, EObjective, GameVersion,
+    GenError, GenerationData, ObjectiveInstance, UDeepDive, UGeneratedMission,
+    DEEP_DIVE_SEED_COUNT,
+};
+
+/// A broken rule, with the stage (0 to 2) it was found in if it is about a single stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub stage: Option<usize>,
+    pub rule: Rule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize)]
+pub enum Rule {
+    #[error("codename {0:?} is not a first and a last codename word")]
+    UnknownName(String),
+    #[error("{0:?} is not a Deep Dive biome")]
+    BiomeNotAvailable(EBiome),
+    #[error("Deep Dives have 3 stages, found {0}")]
+    StageCount(usize),
+    #[error("stage is in {0:?} instead of the biome of the dive")]
+    StageBiome(EBiome),
+    #[error("{template:?} with duration {duration:?} and complexity {complexity:?} is not in the Deep Dive template")]
+    TemplateNotAllowed {
+        template: EMissionTemplate,
+        duration: Option<EMissionDuration>,
+        complexity: Option<EMissionComplexity>,
+    },
+    #[error("{0:?} follows a stage of the same mission type")]
+    ConsecutiveTemplate(EMissionTemplate),
+    #[error("{0:?} can only appear once per Deep Dive")]
+    RepeatedTemplate(EMissionTemplate),
+    #[error("{template:?} has primary objective {expected:?}, found {found:?}")]
+    WrongPrimaryObjective {
+        template: EMissionTemplate,
+        expected: EObjective,
+        found: EObjective,
+    },
+    #[error("Deep Dive stages have 1 secondary objective, found {0}")]
+    SecondaryObjectiveCount(usize),
+    #[error("{objective:?} is not a Deep Dive secondary objective of {template:?}")]
+    SecondaryObjectiveNotAllowed {
+        template: EMissionTemplate,
+        objective: EObjective,
+    },
+    #[error("{objective:?} is banned in {biome:?}")]
+    ObjectiveBannedInBiome {
+        objective: EObjective,
+        biome: EBiome,
+    },
+    #[error("{dna:?} is not a DNA of {template:?}")]
+    DnaNotAllowed {
+        template: EMissionTemplate,
+        dna: EMissionDNA,
+    },
+    #[error("{dna:?} does not fit the duration and complexity limits of the stage")]
+    DnaOutsideLimits { dna: EMissionDNA },
+    #[error("{objective:?} needs {expected} dreadnoughts, found {found:?}")]
+    EliminationTargets {
+        objective: EObjective,
+        expected: usize,
+        found: Vec<crate::EDreadnought>,
+    },
+    #[error("stages have at most 1 mutator, found {0}")]
+    TooManyMutators(usize),
+    #[error("stages have at most 1 warning, found {0}")]
+    TooManyWarnings(usize),
+    #[error("{0:?} is not a Deep Dive mutator")]
+    MutatorNotAvailable(EMissionMutator),
+    #[error("{0:?} is not a Deep Dive warning")]
+    WarningNotAvailable(EMissionWarning),
+    #[error("{0:?} appears in more than one stage")]
+    RepeatedMutator(EMissionMutator),
+    #[error("{0:?} appears in more than one stage")]
+    RepeatedWarning(EMissionWarning),
+    #[error("{mutator:?} is banned with {objective:?}")]
+    MutatorBannedWithObjective {
+        mutator: EMissionMutator,
+        objective: EObjective,
+    },
+    #[error("{warning:?} is banned with {objective:?}")]
+    WarningBannedWithObjective {
+        warning: EMissionWarning,
+        objective: EObjective,
+    },
+    #[error("{warning:?} is banned with {mutator:?}")]
+    WarningBannedWithMutator {
+        warning: EMissionWarning,
+        mutator: EMissionMutator,
+    },
+    #[error("{0} stages with a mutator is not a possible mutator count")]
+    MutatorCount(usize),
+    #[error("{0} stages with a warning is not a possible warning count")]
+    WarningCount(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeepDiveValidation {
+    pub violations: Vec<Violation>,
+    /// Seeds in `0..DEEP_DIVE_SEED_COUNT` that generate exactly this dive.
+    pub seeds: Vec<u32>,
+}
+
+impl DeepDiveValidation {
+    /// No rule is broken, though the dive may still not come from any seed.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Checks `dive` as the `kind` dive of a pair against the rules of the latest game version, and
+/// searches the seeds that generate it.
+pub fn validate_deep_dive(kind: DeepDiveKind, dive: &UDeepDive) -> DeepDiveValidation {
+    validate_deep_dive_with(GameVersion::LATEST.data(), kind, dive)
+}
+
+/// [`validate_deep_dive`] with arbitrary generation data, such as a loaded [`crate::DataPack`].
+///
+/// # Panics
+///
+/// If the data cannot generate a pair, see [`try_validate_deep_dive_with`].
+pub fn validate_deep_dive_with(
+    data: &GenerationData,
+    kind: DeepDiveKind,
+    dive: &UDeepDive,
+) -> DeepDiveValidation {
+    try_validate_deep_dive_with(data, kind, dive).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// [`validate_deep_dive_with`] that returns the first generation error of the seed search instead
+/// of panicking.
+pub fn try_validate_deep_dive_with(
+    data: &GenerationData,
+    kind: DeepDiveKind,
+    dive: &UDeepDive,
+) -> Result<DeepDiveValidation, GenError> {
+    let violations = deep_dive_violations(data, kind, dive);
+    let seeds = if dive.missions.len() == 3 {
+        try_search_deep_dive_seeds_with(data, 0..DEEP_DIVE_SEED_COUNT, |pair| {
+            if pair.biome(kind) != dive.biome || pair.name(kind) != dive.name {
+                return Ok(false);
+            }
+            for (i, mission) in dive.missions.iter().enumerate() {
+                if pair.try_stage(kind, i)? != mission {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        })?
+    } else {
+        vec![]
+    };
+    Ok(DeepDiveValidation { violations, seeds })
+}
+
+/// Only the rule checks of [`validate_deep_dive_with`], without the seed search.
+pub fn deep_dive_violations(
+    data: &GenerationData,
+    kind: DeepDiveKind,
+    dive: &UDeepDive,
+) -> Vec<Violation> {
+    let mut violations = vec![];
+    let mut violation = |stage, rule| violations.push(Violation { stage, rule });
+
+    let known_name = data.names_first.iter().any(|first| {
+        dive.name
+            .strip_prefix(first)
+            .and_then(|rest| rest.strip_prefix(' '))
+            .is_some_and(|last| data.names_last.contains(&last))
+    });
+    if !known_name {
+        violation(None, Rule::UnknownName(dive.name.clone()));
+    }
+    if !data.biomes.contains(&dive.biome) {
+        violation(None, Rule::BiomeNotAvailable(dive.biome));
+    }
+    if dive.missions.len() != 3 {
+        violation(None, Rule::StageCount(dive.missions.len()));
+    }
+
+    let template = match kind {
+        DeepDiveKind::Normal => data.normal_template,
+        DeepDiveKind::Elite => data.hard_template,
+    };
+    for (i, mission) in dive.missions.iter().enumerate() {
+        let stage = Some(i);
+        if mission.biome != dive.biome {
+            violation(stage, Rule::StageBiome(mission.biome));
+        }
+
+        // same as the generator, allowed durations take precedence over allowed complexities
+        let allowed = template.missions.iter().any(|item| {
+            item.mission == mission.template
+                && match (mission.duration_limit, mission.complexity_limit) {
+                    (Some(d), None) => item.allowed_durations.contains(&d),
+                    (None, Some(c)) => {
+                        item.allowed_durations.is_empty() && item.allowed_complexities.contains(&c)
+                    }
+                    (None, None) => {
+                        item.allowed_durations.is_empty() && item.allowed_complexities.is_empty()
+                    }
+                    (Some(_), Some(_)) => false,
+                }
+        });
+        if !allowed {
+            violation(
+                stage,
+                Rule::TemplateNotAllowed {
+                    template: mission.template,
+                    duration: mission.duration_limit,
+                    complexity: mission.complexity_limit,
+                },
+            );
+        }
+        if i > 0 && dive.missions[i - 1].template == mission.template {
+            violation(stage, Rule::ConsecutiveTemplate(mission.template));
+        }
+        let once = template
+            .missions
+            .iter()
+            .any(|item| item.mission == mission.template && item.can_only_appear_once);
+        if once
+            && dive.missions[..i]
+                .iter()
+                .any(|m| m.template == mission.template)
+        {
+            violation(stage, Rule::RepeatedTemplate(mission.template));
+        }
+
+        for rule in mission_violations(data, mission) {
+            violation(stage, rule);
+        }
+
+        if mission.mutators.len() > 1 {
+            violation(stage, Rule::TooManyMutators(mission.mutators.len()));
+        }
+        if mission.warnings.len() > 1 {
+            violation(stage, Rule::TooManyWarnings(mission.warnings.len()));
+        }
+        for mutator in &mission.mutators {
+            if dive.missions[..i]
+                .iter()
+                .any(|m| m.mutators.contains(mutator))
+            {
+                violation(stage, Rule::RepeatedMutator(*mutator));
+            }
+        }
+        for warning in &mission.warnings {
+            if dive.missions[..i]
+                .iter()
+                .any(|m| m.warnings.contains(warning))
+            {
+                violation(stage, Rule::RepeatedWarning(*warning));
+            }
+        }
+    }
+
+    let in_interval = |interval: &crate::FRandInterval, count: usize| {
+        interval
+            .intervals
+            .iter()
+            .any(|i| (i.range.min..=i.range.max).contains(&(count as i32)))
+    };
+    let mutator_stages = dive.missions.iter().filter(|m| !m.mutators.is_empty());
+    let mutator_stages = mutator_stages.count();
+    if !in_interval(&template.mutator_count, mutator_stages) {
+        violation(None, Rule::MutatorCount(mutator_stages));
+    }
+    let warning_stages = dive.missions.iter().filter(|m| !m.warnings.is_empty());
+    let warning_stages = warning_stages.count();
+    if !in_interval(&template.warning_count, warning_stages) {
+        violation(None, Rule::WarningCount(warning_stages));
+    }
+
+    violations
+}
+
+/// Rules of a single stage that do not depend on the rest of the dive.
+fn mission_violations(data: &GenerationData, mission: &UGeneratedMission) -> Vec<Rule> {
+    let mut rules = vec![];
+    let bans = &data.bans;
+
+    // an unknown template is already reported by the Deep Dive template check
+    if let Some(item) = data.mission_templates.get(&mission.template) {
+        let mission_template = &item.mission_template;
+        let primary = mission.primary_objective.objective();
+        if primary != mission_template.primary_objective {
+            rules.push(Rule::WrongPrimaryObjective {
+                template: mission.template,
+                expected: mission_template.primary_objective,
+                found: primary,
+            });
+        }
+        for secondary in &mission.secondary_objectives {
+            if !mission_template
+                .deep_dive_objectives
+                .contains(&secondary.objective())
+            {
+                rules.push(Rule::SecondaryObjectiveNotAllowed {
+                    template: mission.template,
+                    objective: secondary.objective(),
+                });
+            }
+        }
+
+        if !mission_template.dna.contains(&mission.dna) {
+            rules.push(Rule::DnaNotAllowed {
+                template: mission.template,
+                dna: mission.dna,
+            });
+        } else if let Some(dna) = data.dna.get(&mission.dna) {
+            let fits = mission.duration_limit.is_none_or(|d| d == dna.duration)
+                && mission.complexity_limit.is_none_or(|c| c == dna.complexity);
+            if !fits {
+                rules.push(Rule::DnaOutsideLimits { dna: mission.dna });
+            }
+        }
+    }
+    if mission.secondary_objectives.len() != 1 {
+        rules.push(Rule::SecondaryObjectiveCount(
+            mission.secondary_objectives.len(),
+        ));
+    }
+
+    let objectives: Vec<_> = std::iter::once(&mission.primary_objective)
+        .chain(&mission.secondary_objectives)
+        .collect();
+    for objective in &objectives {
+        if bans.is_objective_banned_in_biome(objective.objective(), mission.biome) {
+            rules.push(Rule::ObjectiveBannedInBiome {
+                objective: objective.objective(),
+                biome: mission.biome,
+            });
+        }
+        if let ObjectiveInstance::Elimination { kind, targets } = objective {
+            let expected = match kind {
+                EObjective::OBJ_Eliminate_Eggs => 2,
+                _ => 1,
+            };
+            let mut distinct = targets.clone();
+            distinct.sort();
+            distinct.dedup();
+            if targets.len() != expected || distinct.len() != expected {
+                rules.push(Rule::EliminationTargets {
+                    objective: *kind,
+                    expected,
+                    found: targets.clone(),
+                });
+            }
+        }
+    }
+
+    for &mutator in &mission.mutators {
+        if !data.deep_dive_settings.mutators.contains(&mutator) {
+            rules.push(Rule::MutatorNotAvailable(mutator));
+        }
+        for objective in &objectives {
+            if bans.is_mutator_banned_with_objective(mutator, objective.objective()) {
+                rules.push(Rule::MutatorBannedWithObjective {
+                    mutator,
+                    objective: objective.objective(),
+                });
+            }
+        }
+    }
+    for &warning in &mission.warnings {
+        if !data.deep_dive_settings.warnings.contains(&warning) {
+            rules.push(Rule::WarningNotAvailable(warning));
+        }
+        for objective in &objectives {
+            if bans.is_warning_banned_with_objective(warning, objective.objective()) {
+                rules.push(Rule::WarningBannedWithObjective {
+                    warning,
+                    objective: objective.objective(),
+                });
+            }
+        }
+        for &mutator in &mission.mutators {
+            if bans.is_warning_banned_with_mutator(warning, mutator) {
+                rules.push(Rule::WarningBannedWithMutator { warning, mutator });
+            }
+        }
+    }
+    rules
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_deep_dive_pair;
+
+    #[test]
+    fn test_generated_dives_are_valid() {
+        let data = GameVersion::LATEST.data();
+        for seed in (0..DEEP_DIVE_SEED_COUNT).step_by(97) {
+            let (normal, elite) = gen_deep_dive_pair(seed);
+            for (kind, dive) in [(DeepDiveKind::Normal, normal), (DeepDiveKind::Elite, elite)] {
+                assert_eq!(deep_dive_violations(data, kind, &dive), vec![], "{seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let (_, elite) = gen_deep_dive_pair(216);
+        let validation = validate_deep_dive(DeepDiveKind::Elite, &elite);
+        assert!(validation.is_valid());
+        assert!(validation.seeds.contains(&216));
+
+        let mut broken = elite.clone();
+        broken.name = "Not A Codename".to_string();
+        broken.missions[1].template = broken.missions[0].template;
+        broken.missions[0].mutators = vec![EMissionMutator::MMUT_ExplosiveEnemies];
+        broken.missions[2].mutators = vec![
+            EMissionMutator::MMUT_ExplosiveEnemies,
+            EMissionMutator::MMUT_LowGravity,
+        ];
+        let validation = validate_deep_dive(DeepDiveKind::Elite, &broken);
+        assert!(validation.seeds.is_empty());
+        let rules: Vec<_> = validation.violations.iter().map(|v| &v.rule).collect();
+        assert!(rules.contains(&&Rule::UnknownName("Not A Codename".to_string())));
+        assert!(rules.contains(&&Rule::ConsecutiveTemplate(broken.missions[0].template)));
+        assert!(rules.contains(&&Rule::TooManyMutators(2)));
+        assert!(rules.contains(&&Rule::RepeatedMutator(
+            EMissionMutator::MMUT_ExplosiveEnemies
+        )));
+        assert!(validation
+            .violations
+            .iter()
+            .any(|v| v.stage == Some(1) && matches!(v.rule, Rule::WrongPrimaryObjective { .. })));
+    }
+}
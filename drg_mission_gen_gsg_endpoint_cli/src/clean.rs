@@ -2,10 +2,12 @@
 //! [`DeepDive`][crate::cleaned_deep_dive::DeepDive] that's easier to consume.
 
 use thiserror::Error;
+use tracing::warn;
 
 use drg_mission_gen_core::{
-    EBiome, EMissionComplexity, EMissionDuration, EMissionMutator, EMissionWarning, EObjective,
-    ObjectiveInstance, UDeepDive, UGeneratedMission, UMissionDNA,
+    EBiome, EMissionComplexity, EMissionDNA, EMissionDuration, EMissionMutator, EMissionTemplate,
+    EMissionWarning, EObjective, Language, ObjectiveInstance, UDeepDive, UGeneratedMission,
+    UMissionDNA,
 };
 
 use crate::cleaned_deep_dive::{
@@ -32,15 +34,69 @@ pub enum CleanError {
 
     #[error("only expected at most 1 warning, but was given {count}")]
     TooManyWarnings { count: usize },
+
+    #[error("unexpected duration/complexity for `{objective}`: {duration:?}, {complexity:?}")]
+    UnexpectedDna {
+        objective: &'static str,
+        duration: Duration,
+        complexity: Complexity,
+    },
+}
+
+impl CleanError {
+    /// Internal name of the item that has no cleaned counterpart.
+    fn unmapped(&self) -> Option<&'static str> {
+        match self {
+            CleanError::UnexpectedPrimaryObjective(name)
+            | CleanError::UnexpectedSecondaryObjective(name)
+            | CleanError::UnexpectedMutator(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// How cleaning treats objectives, mutators and DNA it does not know, e.g. after a game update.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum CleanMode {
+    /// Fail with a [`CleanError`].
+    Strict,
+    /// Log a warning and keep them as `Unknown` variants with their internal name.
+    Lenient,
+}
+
+impl CleanMode {
+    fn recover<T>(
+        self,
+        result: Result<T, CleanError>,
+        unknown: impl FnOnce(&'static str) -> T,
+    ) -> Result<T, CleanError> {
+        match result {
+            Err(err) if self == CleanMode::Lenient => match err.unmapped() {
+                Some(name) => {
+                    warn!("{err}, keeping it as unknown");
+                    Ok(unknown(name))
+                }
+                None => Err(err),
+            },
+            result => result,
+        }
+    }
 }
 
 pub(crate) fn clean_unreal_deep_dive(dd: &UDeepDive) -> Result<DeepDive, CleanError> {
+    clean_unreal_deep_dive_with(dd, CleanMode::Strict)
+}
+
+pub(crate) fn clean_unreal_deep_dive_with(
+    dd: &UDeepDive,
+    mode: CleanMode,
+) -> Result<DeepDive, CleanError> {
     let name = dd.name.clone();
     let biome = map_biome(&dd.biome);
     let missions = dd
         .missions
         .iter()
-        .map(map_mission)
+        .map(|mission| map_mission_with(mission, mode))
         .collect::<Result<Vec<Mission>, CleanError>>()?;
     assert_eq!(
         missions.len(),
@@ -74,9 +130,24 @@ pub(crate) fn map_biome(biome: &EBiome) -> Biome {
 }
 
 pub(crate) fn map_mission(mission: &UGeneratedMission) -> Result<Mission, CleanError> {
-    let primary_objective = map_primary_objective(&mission.primary_objective)?;
-    let secondary_objective = map_secondary_objective(&mission.secondary_objectives)?;
-    let mutator = map_mutator(&mission.mutators)?;
+    map_mission_with(mission, CleanMode::Strict)
+}
+
+pub(crate) fn map_mission_with(
+    mission: &UGeneratedMission,
+    mode: CleanMode,
+) -> Result<Mission, CleanError> {
+    let primary_objective = mode.recover(
+        map_primary_objective(&mission.primary_objective),
+        PrimaryObjective::Unknown,
+    )?;
+    let secondary_objective = mode.recover(
+        map_secondary_objective(&mission.secondary_objectives),
+        DeepDiveSecondaryObjective::Unknown,
+    )?;
+    let mutator = mode.recover(map_mutator(&mission.mutators), |name| {
+        Some(Mutator::Unknown(name))
+    })?;
     let warning = map_warning(&mission.warnings)?;
 
     // FIXME(jieyouxu): which complexity/duration takes precedence? are they guaranteed to be
@@ -90,6 +161,20 @@ pub(crate) fn map_mission(mission: &UGeneratedMission) -> Result<Mission, CleanE
     // complexity/duration take precedence?
     let complexity = map_complexity(complexity);
     let duration = map_duration(duration);
+    if primary_objective
+        .detailed_name(complexity, duration, Language::English)
+        .is_none()
+    {
+        let err = CleanError::UnexpectedDna {
+            objective: primary_objective.display(),
+            duration,
+            complexity,
+        };
+        match mode {
+            CleanMode::Strict => return Err(err),
+            CleanMode::Lenient => warn!("{err}"),
+        }
+    }
 
     Ok(Mission {
         primary_objective,
@@ -226,6 +311,24 @@ pub(crate) fn map_warning(warnings: &[EMissionWarning]) -> Result<Option<Warning
     }
 }
 
+/// DNA of `template` with the given complexity and duration.
+pub(crate) fn find_dna(
+    template: EMissionTemplate,
+    complexity: Complexity,
+    duration: Duration,
+) -> Option<EMissionDNA> {
+    template
+        .get()
+        .mission_template
+        .dna
+        .iter()
+        .copied()
+        .find(|dna| {
+            map_complexity(&dna.get().complexity) == complexity
+                && map_duration(&dna.get().duration) == duration
+        })
+}
+
 pub(crate) fn map_complexity(complexity: &EMissionComplexity) -> Complexity {
     match complexity {
         EMissionComplexity::MD_Complexity_Complex => Complexity::Complex,
@@ -240,4 +343,51 @@ pub(crate) fn map_duration(duration: &EMissionDuration) -> Duration {
         EMissionDuration::MD_Duration_Normal => Duration::Normal,
         EMissionDuration::MD_Duration_Short => Duration::Short,
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use drg_mission_gen_core::gen_deep_dive_pair;
+
+    use super::*;
+
+    #[test]
+    fn test_lenient_clean() {
+        let (mut normal, _) = gen_deep_dive_pair(216);
+        let mission = &mut normal.missions[0];
+        mission.primary_objective = ObjectiveInstance::Other {
+            kind: EObjective::OBJ_DD_AlienEggs,
+        };
+        mission.mutators = vec![EMissionMutator::MMUT_GoldRush];
+
+        assert!(matches!(
+            clean_unreal_deep_dive(&normal),
+            Err(CleanError::UnexpectedPrimaryObjective("OBJ_DD_AlienEggs"))
+        ));
+        let dive = clean_unreal_deep_dive_with(&normal, CleanMode::Lenient).unwrap();
+        let mission = &dive.missions[0];
+        assert_eq!(
+            mission.primary_objective,
+            PrimaryObjective::Unknown("OBJ_DD_AlienEggs")
+        );
+        assert_eq!(
+            mission.primary_objective.display_detailed(
+                mission.complexity,
+                mission.duration,
+                Language::English
+            ),
+            "OBJ_DD_AlienEggs"
+        );
+        assert_eq!(mission.mutator, Some(Mutator::Unknown("MMUT_GoldRush")));
+        assert_eq!(mission.mutator.unwrap().display(), "MMUT_GoldRush");
+
+        assert_eq!(
+            PrimaryObjective::DeepScan.display_detailed(
+                Complexity::Complex,
+                Duration::Long,
+                Language::English
+            ),
+            "Deep Scan (Long, Complex)"
+        );
+    }
+}
@@ -2,11 +2,15 @@
 //! [`UDeepDive`][drg_mission_gen_core::UDeepDive] instances.
 
 use serde::{Deserialize, Serialize};
-use strum::IntoStaticStr;
+use strum::{IntoStaticStr, VariantArray};
 
-use drg_mission_gen_core::EDreadnought;
+use drg_mission_gen_core::{
+    EBiome, EDreadnought, EMissionMutator, EMissionTemplate, EMissionWarning, EObjective, Language,
+};
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+use crate::clean::{find_dna, map_biome, map_mutator, map_warning};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct DeepDive {
     pub(crate) name: String,
     pub(crate) seed: u32,
@@ -14,7 +18,19 @@ pub(crate) struct DeepDive {
     pub(crate) missions: Vec<Mission>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    IntoStaticStr,
+    clap::ValueEnum,
+)]
 pub(crate) enum Biome {
     CrystallineCaverns,
     FungusBogs,
@@ -31,23 +47,22 @@ pub(crate) enum Biome {
 
 impl Biome {
     pub(crate) fn display(self) -> &'static str {
-        match self {
-            Biome::CrystallineCaverns => "Crystalline Caverns",
-            Biome::FungusBogs => "Fungus Bogs",
-            Biome::MagmaCore => "Magma Core",
-            Biome::RadioactiveExclusionZone => "Radioactive Exclusion Zone",
-            Biome::DenseBiozone => "Dense Biozone",
-            Biome::SandblastedCorridors => "Sandblasted Corridors",
-            Biome::SaltPits => "Salt Pits",
-            Biome::GlacialStrata => "Glacial Strata",
-            Biome::AzureWeald => "Azure Weald",
-            Biome::HollowBough => "Hollow Bough",
-            Biome::OssuaryDepths => "Ossuary Depths",
-        }
+        self.display_in(Language::English)
+    }
+
+    pub(crate) fn display_in(self, language: Language) -> &'static str {
+        self.unreal().display_name_in(language)
+    }
+
+    pub(crate) fn unreal(self) -> EBiome {
+        *EBiome::VARIANTS
+            .iter()
+            .find(|b| map_biome(b) == self)
+            .expect("every biome is cleaned")
     }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Mission {
     pub(crate) primary_objective: PrimaryObjective,
     pub(crate) secondary_objective: DeepDiveSecondaryObjective,
@@ -57,20 +72,21 @@ pub(crate) struct Mission {
     pub(crate) duration: Duration,
 }
 
-fn format_elimination_targets(targets: &[EDreadnought]) -> String {
+fn format_elimination_targets(targets: &[EDreadnought], language: Language) -> String {
     let list = targets
         .iter()
-        .map(|d| match d {
-            EDreadnought::Dreadnought => "Classic",
-            EDreadnought::Hiveguard => "Hiveguard",
-            EDreadnought::Twins => "Twins",
-        })
+        .map(|d| d.display_name_in(language))
         .collect::<Vec<_>>()
         .join("+");
-    format!("Dreadnought x{} ({})", targets.len(), list)
+    format!(
+        "{} x{} ({})",
+        EObjective::OBJ_DD_Elimination_Eggs.display_name_in(language),
+        targets.len(),
+        list
+    )
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, IntoStaticStr)]
 pub(crate) enum PrimaryObjective {
     DeepScan,
     EscortDuty,
@@ -82,120 +98,82 @@ pub(crate) enum PrimaryObjective {
     Salvage,
     HeavyExtraction,
     Elimination { targets: Vec<EDreadnought> },
+    /// Objective added to the game after this was written, with its internal name. Only produced
+    /// by lenient cleaning.
+    #[serde(skip_deserializing)]
+    Unknown(#[serde(skip_deserializing)] &'static str),
 }
 
 impl PrimaryObjective {
     pub(crate) fn display(&self) -> &'static str {
-        match self {
-            PrimaryObjective::DeepScan => "Deep Scan",
-            PrimaryObjective::EscortDuty => "Escort Duty",
-            PrimaryObjective::MiningExpedition => "Mining Expedition",
-            PrimaryObjective::IndustrialSabotage => "Industrial Sabotage",
-            PrimaryObjective::EggHunt => "Egg Hunt",
-            PrimaryObjective::PointExtraction => "Point Extraction",
-            PrimaryObjective::Refinery => "On-Site Refinery",
-            PrimaryObjective::Salvage => "Salvage Operation",
-            PrimaryObjective::HeavyExtraction => "Heavy Extraction",
-            PrimaryObjective::Elimination { .. } => "Elimination",
-        }
+        self.display_in(Language::English)
     }
 
-    /// Reference: <https://deeprockgalactic.wiki.gg/wiki/Missions>.
-    pub(crate) fn display_detailed(&self, complexity: Complexity, duration: Duration) -> String {
-        match self {
-            PrimaryObjective::DeepScan => match (duration, complexity) {
-                (Duration::Short, Complexity::Average) => "Perform 3 Deep Scans".to_string(),
-                (Duration::Normal, Complexity::Average) => "Perform 5 Deep Scans".to_string(),
-                (dur, comp) => unreachable!(
-                    "unexpected deep scan duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                ),
-            },
-            PrimaryObjective::EscortDuty => match (duration, complexity) {
-                (Duration::Normal, Complexity::Average | Complexity::Complex) => "Escort Duty".to_string(),
-                (dur, comp) => unreachable!(
-                    "unexpected escort duty duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                ),
-            },
-            PrimaryObjective::MiningExpedition => {
-                match (duration, complexity) {
-                    (Duration::Short, Complexity::Simple) => "200 Morkite".to_string(),
-                    (Duration::Normal, Complexity::Simple) => "225 Morkite".to_string(),
-                    (Duration::Normal, Complexity::Average) => "250 Morkite".to_string(),
-                    (Duration::Long, Complexity::Average) => "325 Morkite".to_string(),
-                    (Duration::Long, Complexity::Complex) => "400 Morkite".to_string(),
-                    (dur, comp) => unreachable!(
-                        "unexpected escort duty duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                    ),
-                }
-            },
-            PrimaryObjective::IndustrialSabotage => {
-                match (duration, complexity) {
-                    (Duration::Normal, Complexity::Simple | Complexity::Average) => "Industrial Sabotage".to_string(),
-                    (dur, comp) => unreachable!(
-                        "unexpected industrial sabotage duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                    ),
-                }
-            },
-            PrimaryObjective::EggHunt => {
-                match (duration, complexity) {
-                    (Duration::Short, Complexity::Simple) => "4 Eggs".to_string(),
-                    (Duration::Normal, Complexity::Average) => "6 Eggs".to_string(),
-                    (Duration::Long, Complexity::Average) => "8 Eggs".to_string(),
-                    (dur, comp) => unreachable!(
-                        "unexpected egg hunt duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                    ),
-                }
-            },
-            PrimaryObjective::PointExtraction => {
-                match (duration, complexity) {
-                    (Duration::Normal, Complexity::Complex) => "7 Aquarqs".to_string(),
-                    (Duration::Long, Complexity::Complex) => "10 Aquarqs".to_string(),
-                    (dur, comp) => unreachable!(
-                        "unexpected point extraction duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                    ),
-                }
-            },
-            PrimaryObjective::Refinery => {
-                match (duration, complexity) {
-                    (Duration::Normal, Complexity::Average | Complexity::Complex) => "On-Site Refinery".to_string(),
-                    (dur, comp) => unreachable!(
-                        "unexpected refinery duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                    ),
-                }
-            },
-            PrimaryObjective::Salvage => {
-                match (duration, complexity) {
-                    (Duration::Normal, Complexity::Average) => "2 Mini-M.U.L.E.s & Uplink".to_string(),
-                    (Duration::Long, Complexity::Complex) => "3 Mini-M.U.L.E.s & Uplink".to_string(),
-                    (dur, comp) => unreachable!(
-                        "unexpected point extraction duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                    ),
-                }
-            },
-            PrimaryObjective::HeavyExtraction => match (duration, complexity) {
-                (Duration::Normal, Complexity::Average) => "3 Resinite Masses".to_string(),
-                (Duration::Normal, Complexity::Complex) => "3 Resinite Masses".to_string(),
-                (Duration::Long, Complexity::Average) => "4 Resinite Masses".to_string(),
-                (Duration::Long, Complexity::Complex) => "4 Resinite Masses".to_string(),
-                (dur, comp) => unreachable!(
-                    "unexpected heavy extraction duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                ),
-            },
+    pub(crate) fn display_in(&self, language: Language) -> &'static str {
+        self.template()
+            .map_or_else(|name| name, |template| template.display_name_in(language))
+    }
+
+    /// Mission template of the objective, or the internal name of an unknown objective.
+    pub(crate) fn template(&self) -> Result<EMissionTemplate, &'static str> {
+        Ok(match self {
+            PrimaryObjective::DeepScan => EMissionTemplate::MissionType_DeepScan,
+            PrimaryObjective::EscortDuty => EMissionTemplate::MissionType_Escort,
+            PrimaryObjective::MiningExpedition => EMissionTemplate::MissionType_Extraction,
+            PrimaryObjective::IndustrialSabotage => EMissionTemplate::MissionType_Facility,
+            PrimaryObjective::EggHunt => EMissionTemplate::MissionType_EggCollection,
+            PrimaryObjective::PointExtraction => EMissionTemplate::MissionType_Motherlode,
+            PrimaryObjective::Refinery => EMissionTemplate::MissionType_Refinery,
+            PrimaryObjective::Salvage => EMissionTemplate::MissionType_Salvage,
+            PrimaryObjective::HeavyExtraction => EMissionTemplate::MissionType_Excavation,
+            PrimaryObjective::Elimination { .. } => EMissionTemplate::MissionType_Elimination,
+            PrimaryObjective::Unknown(name) => return Err(name),
+        })
+    }
+
+    /// Name with the amount to collect, falling back to the name with the duration and complexity
+    /// for combinations the game did not have when this was written.
+    pub(crate) fn display_detailed(
+        &self,
+        complexity: Complexity,
+        duration: Duration,
+        language: Language,
+    ) -> String {
+        self.detailed_name(complexity, duration, language)
+            .unwrap_or_else(|| {
+                let name = self.display_in(language);
+                format!("{name} ({duration:?}, {complexity:?})")
+            })
+    }
+
+    /// Name with the amount the mission's DNA asks for, `None` if the template has no such DNA.
+    pub(crate) fn detailed_name(
+        &self,
+        complexity: Complexity,
+        duration: Duration,
+        language: Language,
+    ) -> Option<String> {
+        let template = match self {
             PrimaryObjective::Elimination { targets } => {
-                format_elimination_targets(targets)
-                // match (duration, complexity) {
-                //     (Duration::Normal, Complexity::Average) => "2 Dreadnoughts".to_string(),
-                //     (Duration::Long, Complexity::Complex) => "3 Dreadnoughts".to_string(),
-                //     (dur, comp) => unreachable!(
-                //         "unexpected elimination duration/complexity combination: duration={dur:?}, complexity={comp:?}",
-                //     ),
-                // }
+                return Some(format_elimination_targets(targets, language))
             }
-        }
+            PrimaryObjective::Unknown(name) => return Some(name.to_string()),
+            known => known.template().ok()?,
+        };
+        let dna = find_dna(template, complexity, duration)?;
+        let name = match dna.get().requirement {
+            None => self.display_in(language).to_string(),
+            Some(requirement) => template
+                .get()
+                .mission_template
+                .primary_objective
+                .goal_in(requirement, language),
+        };
+        Some(name)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, IntoStaticStr)]
 pub(crate) enum DeepDiveSecondaryObjective {
     Eggs,
     DeepScan,
@@ -205,47 +183,80 @@ pub(crate) enum DeepDiveSecondaryObjective {
     Pumpjack,
     Minimules,
     Excavation,
+    /// See [`PrimaryObjective::Unknown`].
+    #[serde(skip_deserializing)]
+    Unknown(#[serde(skip_deserializing)] &'static str),
 }
 
 impl DeepDiveSecondaryObjective {
-    pub(crate) fn display(&self) -> String {
-        match self {
-            DeepDiveSecondaryObjective::Eggs => "2 Eggs".to_string(),
-            DeepDiveSecondaryObjective::DeepScan => "2 Resonance Crystals".to_string(),
-            DeepDiveSecondaryObjective::Blackbox => "Black Box".to_string(),
-            DeepDiveSecondaryObjective::Dreadnought { targets } => {
-                format_elimination_targets(targets)
-            }
-            DeepDiveSecondaryObjective::Morkite => "150 Morkite".to_string(),
-            DeepDiveSecondaryObjective::Pumpjack => "Liquid Morkite Well".to_string(),
-            DeepDiveSecondaryObjective::Minimules => "2 Mini-M.U.L.E.s".to_string(),
-            DeepDiveSecondaryObjective::Excavation => "Resinite Mass".to_string(),
+    pub(crate) fn display_in(&self, language: Language) -> String {
+        if let DeepDiveSecondaryObjective::Dreadnought { targets } = self {
+            return format_elimination_targets(targets, language);
         }
+        match self.objective() {
+            Ok(objective) => match objective.deep_dive_requirement() {
+                Some(requirement) => requirement.display_in(language),
+                None => objective.display_name_in(language).to_string(),
+            },
+            Err(name) => name.to_string(),
+        }
+    }
+
+    /// Objective of the game, or the internal name of an unknown objective.
+    pub(crate) fn objective(&self) -> Result<EObjective, &'static str> {
+        Ok(match self {
+            DeepDiveSecondaryObjective::Eggs => EObjective::OBJ_DD_AlienEggs,
+            DeepDiveSecondaryObjective::DeepScan => EObjective::OBJ_DD_DeepScan,
+            DeepDiveSecondaryObjective::Blackbox => EObjective::OBJ_DD_Defense,
+            DeepDiveSecondaryObjective::Dreadnought { .. } => EObjective::OBJ_DD_Elimination_Eggs,
+            DeepDiveSecondaryObjective::Morkite => EObjective::OBJ_DD_Morkite,
+            DeepDiveSecondaryObjective::Pumpjack => EObjective::OBJ_DD_MorkiteWell,
+            DeepDiveSecondaryObjective::Minimules => EObjective::OBJ_DD_RepairMinimules,
+            DeepDiveSecondaryObjective::Excavation => EObjective::OBJ_DD_Excavation,
+            DeepDiveSecondaryObjective::Unknown(name) => return Err(name),
+        })
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, IntoStaticStr,
+)]
 pub(crate) enum Mutator {
     VolatileGuts,
     RichAtmosphere,
     CriticalWeakness,
     BloodSugar,
     LowGravity,
+    /// See [`PrimaryObjective::Unknown`].
+    #[serde(skip_deserializing)]
+    Unknown(#[serde(skip_deserializing)] &'static str),
 }
 
 impl Mutator {
     pub(crate) fn display(self) -> &'static str {
+        self.display_in(Language::English)
+    }
+
+    pub(crate) fn display_in(self, language: Language) -> &'static str {
+        self.unreal()
+            .map_or_else(|name| name, |mutator| mutator.display_name_in(language))
+    }
+
+    /// Mutator of the game, or the internal name of an unknown mutator.
+    pub(crate) fn unreal(self) -> Result<EMissionMutator, &'static str> {
         match self {
-            Mutator::VolatileGuts => "Volatile Guts",
-            Mutator::RichAtmosphere => "Rich Atmosphere",
-            Mutator::CriticalWeakness => "Critical Weakness",
-            Mutator::BloodSugar => "Blood Sugar",
-            Mutator::LowGravity => "Low Gravity",
+            Mutator::Unknown(name) => Err(name),
+            known => Ok(*EMissionMutator::VARIANTS
+                .iter()
+                .find(|m| map_mutator(&[**m]).ok().flatten() == Some(known))
+                .expect("every known mutator is cleaned")),
         }
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, IntoStaticStr,
+)]
 pub(crate) enum Warning {
     RegenerativeBugs,
     EliteThreat,
@@ -268,36 +279,33 @@ pub(crate) enum Warning {
 
 impl Warning {
     pub(crate) fn display(self) -> &'static str {
-        match self {
-            Warning::RegenerativeBugs => "Regenerative Bugs",
-            Warning::EliteThreat => "Elite Threat",
-            Warning::MacteraPlague => "Mactera Plague",
-            Warning::EboniteOutbreak => "Ebonite Outbreak",
-            Warning::DuckAndCover => "Duck and Cover",
-            Warning::CaveLeechCluster => "Cave Leech Cluster",
-            Warning::LowOxygen => "Low Oxygen",
-            Warning::LithophageOutbreak => "Lithophage Outbreak",
-            Warning::ExploderInfestation => "Exploder Infestation",
-            Warning::HauntedCave => "Haunted Cave",
-            Warning::LethalEnemies => "Lethal Enemies",
-            Warning::ShieldDisruption => "Shield Disruption",
-            Warning::Parasites => "Parasites",
-            Warning::Swarmageddon => "Swarmageddon",
-            Warning::RivalPresence => "Rival Presence",
-            Warning::PitJawColony => "Pit Jaw Colony",
-            Warning::ScrabNestingGrounds => "Scrab Nesting Grounds",
-        }
+        self.display_in(Language::English)
+    }
+
+    pub(crate) fn display_in(self, language: Language) -> &'static str {
+        self.unreal().display_name_in(language)
+    }
+
+    pub(crate) fn unreal(self) -> EMissionWarning {
+        *EMissionWarning::VARIANTS
+            .iter()
+            .find(|w| map_warning(&[**w]).ok().flatten() == Some(self))
+            .expect("every warning is cleaned")
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize, IntoStaticStr)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, IntoStaticStr,
+)]
 pub(crate) enum Complexity {
     Simple,
     Average,
     Complex,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize, IntoStaticStr)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, IntoStaticStr,
+)]
 pub(crate) enum Duration {
     Short,
     Normal,
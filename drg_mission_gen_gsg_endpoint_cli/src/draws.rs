@@ -0,0 +1,102 @@
+//! `draws` command: logs every `FRandomStream` draw behind a Deep Dive pair, or compares them with
+//! a trace captured from the game and points at the first draw that differs.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use drg_mission_gen_core::rand::Draw;
+use drg_mission_gen_core::{
+    first_divergence, parse_game_trace, record_deep_dive_pair_draws, Divergence,
+};
+
+use crate::gsg_endpoint;
+use crate::search::parse_seed;
+use crate::Format;
+
+/// Matching draws shown before the first difference.
+const CONTEXT_DRAWS: usize = 5;
+
+#[derive(Debug, clap::Args)]
+pub struct DrawsArgs {
+    /// Seed to generate instead of this week's seed from the GSG endpoint.
+    #[arg(value_parser = parse_seed)]
+    pub(crate) seed: Option<u32>,
+    /// Game trace to compare with, one draw per line ending in the seed after the draw, e.g.
+    /// `MUTATE SEED 1234567`.
+    #[arg(long, value_name = "FILE")]
+    pub(crate) against: Option<PathBuf>,
+}
+
+pub(crate) fn draws(args: &DrawsArgs, format: Format) -> anyhow::Result<String> {
+    let seed = match args.seed {
+        Some(seed) => seed,
+        None => {
+            gsg_endpoint::query_gsg_deep_dive_endpoint()
+                .context("querying GSG deep dive endpoint")?
+                .seed_v2 as u32
+        }
+    };
+    let (_, draws) = record_deep_dive_pair_draws(seed).context("generating deep dives")?;
+
+    let Some(path) = &args.against else {
+        return Ok(match format {
+            Format::Json => serde_json::to_string_pretty(&draws)?,
+            Format::Plain | Format::Discord => {
+                let mut out = String::new();
+                for (i, draw) in draws.iter().enumerate() {
+                    let _ = writeln!(out, "{i:>4} {draw}");
+                }
+                out
+            }
+            Format::Csv => return Err(format.unsupported("draws")),
+        });
+    };
+
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let traced = parse_game_trace(&text).with_context(|| format!("parsing {}", path.display()))?;
+    let divergence = first_divergence(&draws, &traced);
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&divergence)?,
+        Format::Plain | Format::Discord => match divergence {
+            None => format!("all {} draws match", draws.len()),
+            Some(divergence) => format_divergence(&draws, &divergence),
+        },
+        Format::Csv => return Err(format.unsupported("draws")),
+    })
+}
+
+fn format_divergence(draws: &[Draw], divergence: &Divergence) -> String {
+    let mut out = format!(
+        "first difference at draw {} of {}\n",
+        divergence.index,
+        draws.len()
+    );
+    let start = divergence.index.saturating_sub(CONTEXT_DRAWS);
+    for (i, draw) in draws[start..divergence.index].iter().enumerate() {
+        let _ = writeln!(out, "  {:>4} {draw}", start + i);
+    }
+    match divergence.recorded {
+        Some(draw) => {
+            let _ = writeln!(out, "> {:>4} {draw}", divergence.index);
+        }
+        None => {
+            let _ = writeln!(out, "> {:>4} recording ended", divergence.index);
+        }
+    }
+    match divergence.traced {
+        Some(traced) => {
+            let _ = writeln!(
+                out,
+                "  game has seed {} (line {})",
+                traced.seed, traced.line
+            );
+        }
+        None => {
+            let _ = writeln!(out, "  game trace ended");
+        }
+    }
+    out
+}
@@ -0,0 +1,123 @@
+//! `--explain`: shows every random decision behind a Deep Dive pair, with the candidates, their
+//! odds and the fraction drawn.
+
+use std::fmt::Write;
+
+use drg_mission_gen_core::{
+    explain_deep_dive_pair, Choice, Decision, DeepDiveExplanation, DeepDiveKind, UDeepDive,
+};
+
+use crate::clean::map_biome;
+use crate::Format;
+
+/// Draws with more candidates than this, like the codename words, only show the picked one.
+const MAX_LISTED_CANDIDATES: usize = 16;
+
+pub(crate) fn explain(seed: u32, format: Format) -> anyhow::Result<String> {
+    let explanation = explain_deep_dive_pair(seed)?;
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&explanation)?,
+        Format::Plain | Format::Discord => format_tree(&explanation),
+        Format::Csv => return Err(format.unsupported("--explain")),
+    })
+}
+
+fn format_tree(explanation: &DeepDiveExplanation) -> String {
+    let mut out = format!("Seed {}\n", explanation.seed);
+    for (kind, title, dive) in [
+        (
+            DeepDiveKind::Normal,
+            "Normal Deep Dive",
+            &explanation.normal,
+        ),
+        (DeepDiveKind::Elite, "Elite Deep Dive", &explanation.elite),
+    ] {
+        let decisions: Vec<_> = explanation
+            .decisions
+            .iter()
+            .filter(|d| d.kind == kind)
+            .collect();
+        format_dive(&mut out, title, dive, &decisions);
+    }
+    out
+}
+
+fn format_dive(out: &mut String, title: &str, dive: &UDeepDive, decisions: &[&Decision]) {
+    let _ = writeln!(
+        out,
+        "\n{title}: {} in {}",
+        dive.name,
+        map_biome(&dive.biome).display()
+    );
+    for decision in decisions.iter().filter(|d| d.stage.is_none()) {
+        format_decision(out, "  ", decision);
+    }
+    for (i, mission) in dive.missions.iter().enumerate() {
+        let _ = writeln!(out, "  Stage {}: {:?}", i + 1, mission.template);
+        for decision in decisions.iter().filter(|d| d.stage == Some(i)) {
+            format_decision(out, "    ", decision);
+        }
+    }
+}
+
+fn format_decision(out: &mut String, indent: &str, decision: &Decision) {
+    let _ = writeln!(
+        out,
+        "{indent}{:?}: {} ({:.2}%, fraction {:.6})",
+        decision.step,
+        display_choice(decision.picked()),
+        decision.probability() * 100.0,
+        decision.fraction,
+    );
+    if decision.candidates.len() > MAX_LISTED_CANDIDATES {
+        let _ = writeln!(
+            out,
+            "{indent}  out of {} candidates",
+            decision.candidates.len()
+        );
+        return;
+    }
+    let total: f32 = decision.candidates.iter().map(|c| c.weight).sum();
+    for (i, candidate) in decision.candidates.iter().enumerate() {
+        let marker = if i == decision.picked { '*' } else { '-' };
+        let odds = if candidate.weight == 0.0 {
+            "filtered out".to_string()
+        } else {
+            format!("{:.2}%", candidate.weight / total * 100.0)
+        };
+        let _ = writeln!(
+            out,
+            "{indent}  {marker} {} [{odds}]",
+            display_choice(&candidate.value)
+        );
+    }
+}
+
+fn display_choice(choice: &Choice) -> String {
+    match choice {
+        Choice::Biome(biome) => map_biome(biome).display().to_string(),
+        Choice::Name(name) => name.to_string(),
+        Choice::Interval { min, max } => format!("{min} to {max}"),
+        Choice::Count(count) => count.to_string(),
+        Choice::Stage(stage) => format!("stage {}", stage + 1),
+        Choice::Mission {
+            template,
+            duration,
+            complexity,
+        } => {
+            let mut s = format!("{template:?}");
+            if let Some(duration) = duration {
+                let _ = write!(s, " {duration:?}");
+            }
+            if let Some(complexity) = complexity {
+                let _ = write!(s, " {complexity:?}");
+            }
+            s
+        }
+        Choice::Objective(objective) => format!("{objective:?}"),
+        Choice::Mutator(mutator) => format!("{mutator:?}"),
+        Choice::Warning(warning) => format!("{warning:?}"),
+        Choice::Dna(dna) => format!("{dna:?}"),
+        Choice::Dreadnought(dreadnought) => format!("{dreadnought:?}"),
+    }
+}
@@ -0,0 +1,739 @@
+//! Filter expressions for the `search` command, such as
+//! `elite.stage[1].primary == Escort && elite.biome == AzureWeald && !any(secondary == Blackbox)`.
+//!
+//! - `normal.name`, `normal.biome` and `normal.stage[N].<field>` (stages numbered 1 to 3) look at
+//!   one Deep Dive, the same goes for `elite`.
+//! - `any(...)` and `all(...)` check the expression against all six stages. Inside them fields are
+//!   written without a prefix and refer to the stage being checked: `primary`, `secondary`,
+//!   `mutator`, `warning`, `complexity`, `duration`, `dna`, plus `kind`, `name` and `biome` of the
+//!   Deep Dive the stage belongs to.
+//! - Comparisons use `==` and `!=`, and are combined with `&&`, `||`, `!` and parentheses.
+//!
+//! Values are compared by name, ignoring case, spaces and punctuation. A value can be written as
+//! its game name (`MissionType_Escort`), without the prefix (`Escort`), as this CLI calls it
+//! (`EscortDuty`) or as it is displayed (`"Escort Duty"`). A missing mutator or warning is `none`.
+
+use strum::VariantArray;
+use thiserror::Error;
+
+use drg_mission_gen_core::{
+    DeepDiveKind, EBiome, EMissionComplexity, EMissionDNA, EMissionDuration, EMissionMutator,
+    EMissionTemplate, EMissionWarning, EObjective, LazyDeepDivePair, ObjectiveInstance,
+};
+
+use crate::clean::{
+    map_biome, map_complexity, map_duration, map_mutator, map_primary_objective,
+    map_secondary_objective, map_warning,
+};
+
+#[derive(Debug, PartialEq, Error)]
+pub enum FilterError {
+    #[error("syntax error at position {position}: {message}")]
+    Syntax { position: usize, message: String },
+    #[error("unknown field `{0}`")]
+    UnknownField(String),
+    #[error("`{0}` needs `normal.` or `elite.` in front of it outside of any() and all()")]
+    MissingDive(String),
+    #[error("`{value}` is not a known {field}")]
+    UnknownValue { field: &'static str, value: String },
+    #[error("there is no stage {0}, stages are numbered 1 to 3")]
+    NoSuchStage(usize),
+    #[error("any() and all() can not be nested")]
+    NestedQuantifier,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Kind,
+    Name,
+    Biome,
+    Primary,
+    Secondary,
+    Mutator,
+    Warning,
+    Complexity,
+    Duration,
+    Dna,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "kind" => Field::Kind,
+            "name" => Field::Name,
+            "biome" => Field::Biome,
+            "primary" => Field::Primary,
+            "secondary" => Field::Secondary,
+            "mutator" => Field::Mutator,
+            "warning" => Field::Warning,
+            "complexity" => Field::Complexity,
+            "duration" => Field::Duration,
+            "dna" => Field::Dna,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Field::Kind => "kind",
+            Field::Name => "name",
+            Field::Biome => "biome",
+            Field::Primary => "primary",
+            Field::Secondary => "secondary",
+            Field::Mutator => "mutator",
+            Field::Warning => "warning",
+            Field::Complexity => "complexity",
+            Field::Duration => "duration",
+            Field::Dna => "dna",
+        }
+    }
+
+    fn is_stage_field(self) -> bool {
+        !matches!(self, Field::Kind | Field::Name | Field::Biome)
+    }
+
+    /// Resolves a value written in a filter to the values of this field it names.
+    fn resolve(self, value: &str) -> Result<Value, FilterError> {
+        let normalized = normalize(value);
+        let resolved = match self {
+            Field::Name => return Ok(Value::Name(normalized)),
+            Field::Kind => Value::Kind(named(DeepDiveKind::VARIANTS, kind_names, &normalized)),
+            Field::Biome => Value::Biome(named(EBiome::VARIANTS, biome_names, &normalized)),
+            Field::Primary => Value::Primary(named(
+                EMissionTemplate::VARIANTS,
+                primary_names,
+                &normalized,
+            )),
+            Field::Secondary => {
+                let mut objectives: Vec<_> = EMissionTemplate::VARIANTS
+                    .iter()
+                    .flat_map(|t| t.get().mission_template.deep_dive_objectives)
+                    .copied()
+                    .collect();
+                objectives.sort();
+                objectives.dedup();
+                Value::Secondary(named(&objectives, secondary_names, &normalized))
+            }
+            Field::Mutator => {
+                let mutators: Vec<_> = std::iter::once(None)
+                    .chain(EMissionMutator::VARIANTS.iter().copied().map(Some))
+                    .collect();
+                Value::Mutator(named(&mutators, mutator_names, &normalized))
+            }
+            Field::Warning => {
+                let warnings: Vec<_> = std::iter::once(None)
+                    .chain(EMissionWarning::VARIANTS.iter().copied().map(Some))
+                    .collect();
+                Value::Warning(named(&warnings, warning_names, &normalized))
+            }
+            Field::Complexity => Value::Complexity(named(
+                EMissionComplexity::VARIANTS,
+                complexity_names,
+                &normalized,
+            )),
+            Field::Duration => Value::Duration(named(
+                EMissionDuration::VARIANTS,
+                duration_names,
+                &normalized,
+            )),
+            Field::Dna => Value::Dna(named(EMissionDNA::VARIANTS, dna_names, &normalized)),
+        };
+        if resolved.is_empty() {
+            return Err(FilterError::UnknownValue {
+                field: self.name(),
+                value: value.to_string(),
+            });
+        }
+        Ok(resolved)
+    }
+}
+
+/// Every candidate that can be written as `normalized`.
+fn named<T: Copy>(candidates: &[T], names: fn(&T) -> Vec<String>, normalized: &str) -> Vec<T> {
+    candidates
+        .iter()
+        .filter(|c| names(c).iter().any(|n| n == normalized))
+        .copied()
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    /// `normal.biome`, `elite.stage[2].primary`
+    Dive {
+        kind: DeepDiveKind,
+        stage: Option<usize>,
+    },
+    /// The stage `any()`/`all()` is checking.
+    Current,
+}
+
+/// The values a comparison accepts, resolved from the name written in the filter.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Kind(Vec<DeepDiveKind>),
+    /// Normalized, see [`normalize`].
+    Name(String),
+    Biome(Vec<EBiome>),
+    Primary(Vec<EMissionTemplate>),
+    Secondary(Vec<EObjective>),
+    Mutator(Vec<Option<EMissionMutator>>),
+    Warning(Vec<Option<EMissionWarning>>),
+    Complexity(Vec<EMissionComplexity>),
+    Duration(Vec<EMissionDuration>),
+    Dna(Vec<EMissionDNA>),
+}
+
+impl Value {
+    fn is_empty(&self) -> bool {
+        match self {
+            Value::Kind(v) => v.is_empty(),
+            Value::Name(_) => false,
+            Value::Biome(v) => v.is_empty(),
+            Value::Primary(v) => v.is_empty(),
+            Value::Secondary(v) => v.is_empty(),
+            Value::Mutator(v) => v.is_empty(),
+            Value::Warning(v) => v.is_empty(),
+            Value::Complexity(v) => v.is_empty(),
+            Value::Duration(v) => v.is_empty(),
+            Value::Dna(v) => v.is_empty(),
+        }
+    }
+
+    /// Only generates the stages the value looks at.
+    fn matches(
+        &self,
+        pair: &mut LazyDeepDivePair,
+        kind: DeepDiveKind,
+        stage: Option<usize>,
+    ) -> bool {
+        match self {
+            Value::Kind(kinds) => return kinds.contains(&kind),
+            Value::Name(name) => return normalize(&pair.name(kind)) == *name,
+            Value::Biome(biomes) => return biomes.contains(&pair.biome(kind)),
+            _ => {}
+        }
+        let stage = pair.stage(kind, stage.expect("checked when parsing"));
+        match self {
+            Value::Kind(_) | Value::Name(_) | Value::Biome(_) => unreachable!(),
+            Value::Primary(templates) => templates.contains(&stage.template),
+            Value::Secondary(objectives) => stage
+                .secondary_objectives
+                .iter()
+                .any(|o| objectives.contains(&o.objective())),
+            Value::Mutator(mutators) => mutators.contains(&stage.mutators.first().copied()),
+            Value::Warning(warnings) => warnings.contains(&stage.warnings.first().copied()),
+            Value::Complexity(complexities) => complexities.contains(&stage.dna.get().complexity),
+            Value::Duration(durations) => durations.contains(&stage.dna.get().duration),
+            Value::Dna(dna) => dna.contains(&stage.dna),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Any(Box<Expr>),
+    All(Box<Expr>),
+    Compare {
+        target: Target,
+        equal: bool,
+        value: Value,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct Filter(Expr);
+
+impl Filter {
+    pub(crate) fn parse(filter: &str) -> Result<Self, FilterError> {
+        let mut parser = Parser {
+            tokens: tokenize(filter)?,
+            next: 0,
+            end: filter.len(),
+            in_quantifier: false,
+        };
+        let expr = parser.or()?;
+        if let Some((position, token)) = parser.tokens.get(parser.next) {
+            return Err(FilterError::Syntax {
+                position: *position,
+                message: format!("unexpected {token:?}"),
+            });
+        }
+        Ok(Filter(expr))
+    }
+
+    pub(crate) fn matches(&self, pair: &mut LazyDeepDivePair) -> bool {
+        eval(&self.0, pair, None)
+    }
+}
+
+/// All six stages, in the order `any()` and `all()` check them.
+const STAGES: [(DeepDiveKind, usize); 6] = [
+    (DeepDiveKind::Normal, 0),
+    (DeepDiveKind::Normal, 1),
+    (DeepDiveKind::Normal, 2),
+    (DeepDiveKind::Elite, 0),
+    (DeepDiveKind::Elite, 1),
+    (DeepDiveKind::Elite, 2),
+];
+
+fn eval(expr: &Expr, pair: &mut LazyDeepDivePair, current: Option<(DeepDiveKind, usize)>) -> bool {
+    match expr {
+        Expr::Not(e) => !eval(e, pair, current),
+        Expr::And(a, b) => eval(a, pair, current) && eval(b, pair, current),
+        Expr::Or(a, b) => eval(a, pair, current) || eval(b, pair, current),
+        Expr::Any(e) => STAGES.into_iter().any(|stage| eval(e, pair, Some(stage))),
+        Expr::All(e) => STAGES.into_iter().all(|stage| eval(e, pair, Some(stage))),
+        Expr::Compare {
+            target,
+            equal,
+            value,
+        } => {
+            let (kind, stage) = match target {
+                Target::Dive { kind, stage } => (*kind, *stage),
+                Target::Current => {
+                    let (kind, stage) = current.expect("checked when parsing");
+                    (kind, Some(stage))
+                }
+            };
+            value.matches(pair, kind, stage) == *equal
+        }
+    }
+}
+
+/// Game name prefixes that can be left out.
+const PREFIXES: &[&str] = &[
+    "MissionType_",
+    "BIOME_",
+    "OBJ_1st_",
+    "OBJ_2nd_",
+    "OBJ_DD_",
+    "OBJ_",
+    "MMUT_",
+    "WRN_",
+    "DNA_",
+    "MD_Duration_",
+    "MD_Complexity_",
+];
+
+/// Lowercase letters and digits only, so `"Escort Duty"` and `EscortDuty` compare equal.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn names<S: AsRef<str>>(names: impl IntoIterator<Item = S>) -> Vec<String> {
+    let mut all = vec![];
+    for name in names {
+        let name = name.as_ref();
+        all.push(normalize(name));
+        if let Some(short) = PREFIXES.iter().find_map(|p| name.strip_prefix(p)) {
+            all.push(normalize(short));
+        }
+    }
+    all
+}
+
+fn kind_names(kind: &DeepDiveKind) -> Vec<String> {
+    names([format!("{kind:?}")])
+}
+
+fn biome_names(biome: &EBiome) -> Vec<String> {
+    let clean = map_biome(biome);
+    names([
+        format!("{biome:?}"),
+        <&str>::from(clean).into(),
+        clean.display().into(),
+    ])
+}
+
+fn primary_names(template: &EMissionTemplate) -> Vec<String> {
+    let objective = template.get().mission_template.primary_objective;
+    let mut all = vec![format!("{template:?}"), <&str>::from(objective).into()];
+    if let Ok(clean) = map_primary_objective(&ObjectiveInstance::from_objective(objective)) {
+        all.push(<&str>::from(&clean).into());
+        all.push(clean.display().into());
+    }
+    names(all)
+}
+
+fn secondary_names(objective: &EObjective) -> Vec<String> {
+    let mut all = vec![<&str>::from(*objective)];
+    if let Ok(clean) = map_secondary_objective(&[ObjectiveInstance::from_objective(*objective)]) {
+        all.push(<&str>::from(&clean));
+    }
+    names(all)
+}
+
+fn mutator_names(mutator: &Option<EMissionMutator>) -> Vec<String> {
+    let Some(mutator) = mutator else {
+        return names(["none"]);
+    };
+    let mut all = vec![<&str>::from(*mutator)];
+    if let Ok(Some(clean)) = map_mutator(&[*mutator]) {
+        all.push(clean.into());
+        all.push(clean.display());
+    }
+    names(all)
+}
+
+fn warning_names(warning: &Option<EMissionWarning>) -> Vec<String> {
+    let Some(warning) = warning else {
+        return names(["none"]);
+    };
+    let mut all = vec![<&str>::from(*warning)];
+    if let Ok(Some(clean)) = map_warning(&[*warning]) {
+        all.push(clean.into());
+        all.push(clean.display());
+    }
+    names(all)
+}
+
+fn complexity_names(complexity: &EMissionComplexity) -> Vec<String> {
+    let clean: &str = map_complexity(complexity).into();
+    names([format!("{complexity:?}"), clean.into()])
+}
+
+fn duration_names(duration: &EMissionDuration) -> Vec<String> {
+    let clean: &str = map_duration(duration).into();
+    names([format!("{duration:?}"), clean.into()])
+}
+
+fn dna_names(dna: &EMissionDNA) -> Vec<String> {
+    names([format!("{dna:?}")])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Dot,
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+}
+
+fn tokenize(filter: &str) -> Result<Vec<(usize, Token)>, FilterError> {
+    let mut tokens = vec![];
+    let mut chars = filter.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '.' => Token::Dot,
+            '=' | '!' | '&' | '|' => {
+                let next = chars.peek().map(|(_, next)| *next);
+                let token = match (c, next) {
+                    ('=', Some('=')) => Token::Eq,
+                    ('!', Some('=')) => Token::Ne,
+                    ('&', Some('&')) => Token::And,
+                    ('|', Some('|')) => Token::Or,
+                    ('!', _) => {
+                        tokens.push((i, Token::Not));
+                        continue;
+                    }
+                    _ => {
+                        return Err(FilterError::Syntax {
+                            position: i,
+                            message: format!("expected `{c}{c}`"),
+                        })
+                    }
+                };
+                chars.next();
+                token
+            }
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => string.push(c),
+                        None => {
+                            return Err(FilterError::Syntax {
+                                position: i,
+                                message: "unterminated string".to_string(),
+                            })
+                        }
+                    }
+                }
+                Token::Str(string)
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' || c == '-' => {
+                let mut word = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| {
+                    c.is_ascii_alphanumeric() || *c == '_' || *c == '-' || *c == '\''
+                }) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            }
+            c => {
+                return Err(FilterError::Syntax {
+                    position: i,
+                    message: format!("unexpected `{c}`"),
+                })
+            }
+        };
+        tokens.push((i, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    /// Length of the filter, reported as the position of a missing token at the end.
+    end: usize,
+    in_quantifier: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.next).map_or(self.end, |(p, _)| *p)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.next += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: Token, what: &str) -> Result<(), FilterError> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {what}")))
+        }
+    }
+
+    fn word(&mut self, what: &str) -> Result<String, FilterError> {
+        match self.peek() {
+            Some(Token::Word(word)) => {
+                let word = word.clone();
+                self.next += 1;
+                Ok(word)
+            }
+            _ => Err(self.error(format!("expected {what}"))),
+        }
+    }
+
+    fn error(&self, message: String) -> FilterError {
+        FilterError::Syntax {
+            position: self.position(),
+            message,
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, FilterError> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, FilterError> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::LParen) {
+            let expr = self.or()?;
+            self.expect(Token::RParen, "`)`")?;
+            return Ok(expr);
+        }
+        let quantifier = match self.peek() {
+            Some(Token::Word(w)) if w == "any" || w == "all" => Some(w == "any"),
+            _ => None,
+        };
+        if let Some(any) = quantifier {
+            if self.tokens.get(self.next + 1).map(|(_, t)| t) == Some(&Token::LParen) {
+                if self.in_quantifier {
+                    return Err(FilterError::NestedQuantifier);
+                }
+                self.next += 2;
+                self.in_quantifier = true;
+                let expr = Box::new(self.or()?);
+                self.in_quantifier = false;
+                self.expect(Token::RParen, "`)`")?;
+                return Ok(if any {
+                    Expr::Any(expr)
+                } else {
+                    Expr::All(expr)
+                });
+            }
+        }
+        self.compare()
+    }
+
+    fn compare(&mut self) -> Result<Expr, FilterError> {
+        let (target, field) = self.target()?;
+        let equal = if self.eat(&Token::Eq) {
+            true
+        } else if self.eat(&Token::Ne) {
+            false
+        } else {
+            return Err(self.error("expected `==` or `!=`".to_string()));
+        };
+        let value = match self.peek() {
+            Some(Token::Word(value) | Token::Str(value)) => value.clone(),
+            _ => return Err(self.error("expected a value".to_string())),
+        };
+        self.next += 1;
+
+        Ok(Expr::Compare {
+            target,
+            equal,
+            value: field.resolve(&value)?,
+        })
+    }
+
+    fn target(&mut self) -> Result<(Target, Field), FilterError> {
+        let first = self.word("a field")?;
+        let kind = match first.as_str() {
+            "normal" => Some(DeepDiveKind::Normal),
+            "elite" => Some(DeepDiveKind::Elite),
+            _ => None,
+        };
+        let Some(kind) = kind.filter(|_| self.eat(&Token::Dot)) else {
+            let field = Field::parse(&first).ok_or(FilterError::UnknownField(first.clone()))?;
+            if !self.in_quantifier {
+                return Err(FilterError::MissingDive(first));
+            }
+            return Ok((Target::Current, field));
+        };
+
+        let name = self.word("a field")?;
+        if name == "stage" {
+            self.expect(Token::LBracket, "`[`")?;
+            let stage = self.word("a stage number")?;
+            let stage: usize = stage
+                .parse()
+                .map_err(|_| self.error("expected a stage number".to_string()))?;
+            if !(1..=3).contains(&stage) {
+                return Err(FilterError::NoSuchStage(stage));
+            }
+            self.expect(Token::RBracket, "`]`")?;
+            self.expect(Token::Dot, "`.`")?;
+            let name = self.word("a field")?;
+            let field = Field::parse(&name)
+                .filter(|f| f.is_stage_field())
+                .ok_or(FilterError::UnknownField(format!("stage[{stage}].{name}")))?;
+            let target = Target::Dive {
+                kind,
+                stage: Some(stage - 1),
+            };
+            return Ok((target, field));
+        }
+        let field = Field::parse(&name)
+            .filter(|f| !f.is_stage_field() && *f != Field::Kind)
+            .ok_or(FilterError::UnknownField(format!("{first}.{name}")))?;
+        let target = Target::Dive { kind, stage: None };
+        Ok((target, field))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use drg_mission_gen_core::GameVersion;
+
+    fn matches(filter: &str, seed: u32) -> bool {
+        let mut pair = LazyDeepDivePair::new(GameVersion::LATEST.data(), seed);
+        Filter::parse(filter).unwrap().matches(&mut pair)
+    }
+
+    #[test]
+    fn test_matches() {
+        // seed 216: elite "Murky Base" in the Azure Weald, starting with Escort Duty and 150 Morkite
+        let filter = "elite.stage[1].primary == Escort && elite.biome == AzureWeald \
+                      && !any(secondary == Blackbox)";
+        assert!(matches(filter, 216));
+        assert!(matches("elite.stage[1].primary == \"Escort Duty\"", 216));
+        assert!(matches("elite.stage[1].primary == MissionType_Escort", 216));
+        assert!(matches("elite.name == \"murky base\"", 216));
+        assert!(matches("elite.stage[1].secondary == Morkite", 216));
+        assert!(matches("elite.stage[1].mutator == none", 216));
+        assert!(matches("elite.stage[1].warning == Parasites", 216));
+        assert!(matches(
+            "any(kind == normal && mutator == VolatileGuts)",
+            216
+        ));
+        assert!(!matches(
+            "any(kind == elite && mutator == VolatileGuts)",
+            216
+        ));
+        assert!(!matches("all(biome == AzureWeald)", 216));
+        assert!(matches(
+            "elite.biome == \"Crystalline Caverns\" || normal.biome == \"Crystalline Caverns\"",
+            216
+        ));
+        assert!(matches("!(elite.biome != AzureWeald)", 216));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |filter| Filter::parse(filter).unwrap_err();
+        assert_eq!(
+            parse("elite.stage[0].primary == Escort"),
+            FilterError::NoSuchStage(0)
+        );
+        assert_eq!(
+            parse("primary == Escort"),
+            FilterError::MissingDive("primary".to_string())
+        );
+        assert_eq!(
+            parse("elite.biome == Moon"),
+            FilterError::UnknownValue {
+                field: "biome",
+                value: "Moon".to_string()
+            }
+        );
+        assert_eq!(
+            parse("any(all(primary == Escort))"),
+            FilterError::NestedQuantifier
+        );
+        assert_eq!(
+            parse("elite.biome = AzureWeald"),
+            FilterError::Syntax {
+                position: 12,
+                message: "expected `==`".to_string()
+            }
+        );
+        assert_eq!(
+            parse("(elite.biome == AzureWeald"),
+            FilterError::Syntax {
+                position: 26,
+                message: "expected `)`".to_string()
+            }
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use time::OffsetDateTime;
-use drg_mission_gen_core::EDreadnought;
+use drg_mission_gen_core::{EDreadnought, Language};
 
 use crate::cleaned_deep_dive::{
     Complexity, DeepDive, DeepDiveSecondaryObjective, Duration, Mission, Mutator, PrimaryObjective,
@@ -13,14 +13,14 @@ pub(crate) fn format_discord(
     pair: &DeepDivePair,
     start_datetime: OffsetDateTime,
     end_datetime: OffsetDateTime,
+    language: Language,
 ) -> String {
     let start_date = start_datetime.date();
     let end_date = end_datetime.date();
     let end_timestamp = end_datetime.unix_timestamp();
 
-    let seed = pair.normal.seed;
-    let dd_info = format_dive(&pair.normal);
-    let edd_info = format_dive(&pair.elite);
+    let dd_info = format_dive(&pair.normal, language);
+    let edd_info = format_dive(&pair.elite, language);
 
     format!(
         "\
@@ -34,7 +34,7 @@ pub(crate) fn format_discord(
     )
 }
 
-fn format_dive(dive: &DeepDive) -> String {
+pub(crate) fn format_dive(dive: &DeepDive, language: Language) -> String {
     let DeepDive {
         name,
         biome,
@@ -44,7 +44,7 @@ fn format_dive(dive: &DeepDive) -> String {
 
     let basic_info = format!(
         "Region: **{biome}** | Code Name: **{codename}**",
-        biome = biome.display(),
+        biome = biome.display_in(language),
         codename = name
     );
 
@@ -52,29 +52,30 @@ fn format_dive(dive: &DeepDive) -> String {
         unreachable!("expected deep dives to have 3 stages");
     };
 
-    let stage_1 = format_stage(1, stage_1);
-    let stage_2 = format_stage(2, stage_2);
-    let stage_3 = format_stage(3, stage_3);
+    let stage_1 = format_stage(1, stage_1, language);
+    let stage_2 = format_stage(2, stage_2, language);
+    let stage_3 = format_stage(3, stage_3, language);
 
     [basic_info, stage_1, stage_2, stage_3].join("\n")
 }
 
-fn format_stage(stage: usize, mission: &Mission) -> String {
+fn format_stage(stage: usize, mission: &Mission, language: Language) -> String {
     let primary_objective = format_primary_objective(
         &mission.primary_objective,
         mission.complexity,
         mission.duration,
+        language,
     );
-    let secondary_objective = format_secondary_objective(&mission.secondary_objective);
+    let secondary_objective = format_secondary_objective(&mission.secondary_objective, language);
 
     let extra_inner = match (mission.mutator, mission.warning) {
         (Some(mutator), Some(warning)) => {
-            let mutator = format_mutator(mutator);
-            let warning = format_warning(warning);
+            let mutator = format_mutator(mutator, language);
+            let warning = format_warning(warning, language);
             Some(format!("{mutator} {warning}"))
         }
-        (Some(mutator), None) => Some(format_mutator(mutator)),
-        (None, Some(warning)) => Some(format_warning(warning)),
+        (Some(mutator), None) => Some(format_mutator(mutator, language)),
+        (None, Some(warning)) => Some(format_warning(warning, language)),
         (None, None) => None,
     };
     let extra = extra_inner
@@ -95,82 +96,62 @@ fn format_primary_objective(
     obj: &PrimaryObjective,
     complexity: Complexity,
     duration: Duration,
+    language: Language,
 ) -> String {
-    #[allow(clippy::useless_format)]
+    let detailed = || obj.display_detailed(complexity, duration, language);
     match obj {
-        PrimaryObjective::DeepScan => {
-            format!(":ResonanceCrystal: {}", obj.display_detailed(complexity, duration))
-        }
-        PrimaryObjective::EscortDuty => format!(":drill: Escort Duty"),
-        PrimaryObjective::MiningExpedition => {
-            format!(":morkite: {}", obj.display_detailed(complexity, duration))
-        }
-        PrimaryObjective::IndustrialSabotage => format!(":caretaker: Industrial Sabotage"),
-        PrimaryObjective::EggHunt => {
-            format!(":gegg: {}", obj.display_detailed(complexity, duration))
-        }
-        PrimaryObjective::PointExtraction => {
-            format!(":aquarq: {}", obj.display_detailed(complexity, duration))
-        }
-        PrimaryObjective::Refinery => {
-            format!(":refinerywell: On-Site Refining")
-        }
-        PrimaryObjective::Salvage => {
-            format!(":molly: {}", obj.display_detailed(complexity, duration))
-        }
-        PrimaryObjective::HeavyExtraction => {
-            format!(":rosette: {}", obj.display_detailed(complexity, duration))
+        PrimaryObjective::DeepScan => format!(":ResonanceCrystal: {}", detailed()),
+        PrimaryObjective::EscortDuty => format!(":drill: {}", obj.display_in(language)),
+        PrimaryObjective::MiningExpedition => format!(":morkite: {}", detailed()),
+        PrimaryObjective::IndustrialSabotage => {
+            format!(":caretaker: {}", obj.display_in(language))
         }
+        PrimaryObjective::EggHunt => format!(":gegg: {}", detailed()),
+        PrimaryObjective::PointExtraction => format!(":aquarq: {}", detailed()),
+        PrimaryObjective::Refinery => format!(":refinerywell: {}", obj.display_in(language)),
+        PrimaryObjective::Salvage => format!(":molly: {}", detailed()),
+        PrimaryObjective::HeavyExtraction => format!(":rosette: {}", detailed()),
         PrimaryObjective::Elimination { ref targets } => {
-            let target_str = format_elimination_targets(targets);
-            format!(
-                ":dreadegg: {} {}",
-                obj.display_detailed(complexity, duration),
-                target_str
-            )
+            let target_str = format_elimination_targets(targets, language);
+            format!(":dreadegg: {} {}", detailed(), target_str)
         }
+        PrimaryObjective::Unknown(name) => name.to_string(),
     }
 }
 
-fn format_secondary_objective(obj: &DeepDiveSecondaryObjective) -> String {
-    match obj {
-        DeepDiveSecondaryObjective::Eggs => ":gegg: 2 Eggs".to_string(),
-        DeepDiveSecondaryObjective::DeepScan => ":ResonanceCrystal: 2 Resonance Crystals".to_string(),
-        DeepDiveSecondaryObjective::Blackbox => ":uplink: Black Box".to_string(),
+fn format_secondary_objective(obj: &DeepDiveSecondaryObjective, language: Language) -> String {
+    let emoji = match obj {
+        DeepDiveSecondaryObjective::Eggs => ":gegg:",
+        DeepDiveSecondaryObjective::DeepScan => ":ResonanceCrystal:",
+        DeepDiveSecondaryObjective::Blackbox => ":uplink:",
         DeepDiveSecondaryObjective::Dreadnought { targets } => {
-            let target_str = format_elimination_targets(targets);
-            format!(":dreadegg: {}", target_str)
+            let target_str = format_elimination_targets(targets, language);
+            return format!(":dreadegg: {}", target_str);
         }
-        DeepDiveSecondaryObjective::Morkite => ":morkite: 150 Morkite".to_string(),
-        DeepDiveSecondaryObjective::Pumpjack => ":refinerywell: Liquid Morkite Well".to_string(),
-        DeepDiveSecondaryObjective::Minimules => ":molly: 2 Mini-M.U.L.E.s".to_string(),
-        DeepDiveSecondaryObjective::Excavation => ":rosette: Resinite Mass".to_string(),
-    }
-}
-
-fn format_mutator(mutator: Mutator) -> String {
-    format!(":rocknstone: **{}**", mutator.display())
+        DeepDiveSecondaryObjective::Morkite => ":morkite:",
+        DeepDiveSecondaryObjective::Pumpjack => ":refinerywell:",
+        DeepDiveSecondaryObjective::Minimules => ":molly:",
+        DeepDiveSecondaryObjective::Excavation => ":rosette:",
+        DeepDiveSecondaryObjective::Unknown(name) => return name.to_string(),
+    };
+    format!("{emoji} {}", obj.display_in(language))
 }
 
-fn format_warning(warning: Warning) -> String {
-    format!(":tothebone: **{}**", warning.display())
+fn format_mutator(mutator: Mutator, language: Language) -> String {
+    format!(":rocknstone: **{}**", mutator.display_in(language))
 }
 
-fn format_enemy_descriptor(descriptor: EDreadnought) -> &'static str {
-    match descriptor {
-        EDreadnought::Dreadnought => "Classic",
-        EDreadnought::Hiveguard => "Hiveguard",
-        EDreadnought::Twins => "Twins",
-    }
+fn format_warning(warning: Warning, language: Language) -> String {
+    format!(":tothebone: **{}**", warning.display_in(language))
 }
 
-fn format_elimination_targets(targets: &[EDreadnought]) -> String {
+fn format_elimination_targets(targets: &[EDreadnought], language: Language) -> String {
     if targets.is_empty() {
         return String::new();
     }
     let target_strs: Vec<&str> = targets
         .iter()
-        .map(|t| format_enemy_descriptor(*t))
+        .map(|t| t.display_name_in(language))
         .collect();
-    format!("{}", target_strs.join("+"))
+    target_strs.join("+")
 }
\ No newline at end of file
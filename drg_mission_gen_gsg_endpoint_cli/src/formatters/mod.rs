@@ -3,6 +3,8 @@ pub(crate) mod plain;
 
 use tabled::Tabled;
 
+use drg_mission_gen_core::Language;
+
 use crate::cleaned_deep_dive::Mission;
 
 #[derive(Tabled)]
@@ -15,14 +17,14 @@ pub(crate) struct Stage {
     mutator: &'static str,
 }
 
-pub(crate) fn mission_to_stage(i: usize, m: &Mission) -> Stage {
+pub(crate) fn mission_to_stage(i: usize, m: &Mission, language: Language) -> Stage {
     Stage {
         stage: i + 1,
         primary: m
             .primary_objective
-            .display_detailed(m.complexity, m.duration),
-        secondary: m.secondary_objective.display(),
-        warning: m.warning.map(|w| w.display()).unwrap_or(""),
-        mutator: m.mutator.map(|mt| mt.display()).unwrap_or(""),
+            .display_detailed(m.complexity, m.duration, language),
+        secondary: m.secondary_objective.display_in(language),
+        warning: m.warning.map(|w| w.display_in(language)).unwrap_or(""),
+        mutator: m.mutator.map(|mt| mt.display_in(language)).unwrap_or(""),
     }
 }
\ No newline at end of file
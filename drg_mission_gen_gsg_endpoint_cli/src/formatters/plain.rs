@@ -2,6 +2,8 @@ use tabled::settings::Style;
 use tabled::Table;
 use time::OffsetDateTime;
 
+use drg_mission_gen_core::Language;
+
 use crate::cleaned_deep_dive::DeepDive;
 use crate::deep_dive_pair::DeepDivePair;
 
@@ -11,6 +13,7 @@ pub(crate) fn format_plain(
     pair: &DeepDivePair,
     start_datetime: OffsetDateTime,
     end_datetime: OffsetDateTime,
+    language: Language,
 ) -> String {
     let header = "=== Deep Dive Info ===".to_string();
     let start_date = start_datetime.date();
@@ -20,10 +23,10 @@ pub(crate) fn format_plain(
     let seed = format!("Seed: {}", pair.elite.seed);
 
     let normal_dd_title = "=== Normal Deep Dive ===".to_string();
-    let normal_info = format_plain_dd(&pair.normal);
+    let normal_info = format_plain_dd(&pair.normal, language);
 
     let elite_dd_title = "=== Elite Deep Dive ===".to_string();
-    let elite_info = format_plain_dd(&pair.elite);
+    let elite_info = format_plain_dd(&pair.elite, language);
 
     [
         header,
@@ -41,14 +44,14 @@ pub(crate) fn format_plain(
     .join("\n")
 }
 
-fn format_plain_dd(dd: &DeepDive) -> String {
+pub(crate) fn format_plain_dd(dd: &DeepDive, language: Language) -> String {
     let codename = format!("Codename: {}", dd.name);
-    let biome = format!("Biome: {}", dd.biome.display());
+    let biome = format!("Biome: {}", dd.biome.display_in(language));
     let rows = dd
         .missions
         .iter()
         .enumerate()
-        .map(|(i, m)| mission_to_stage(i, m))
+        .map(|(i, m)| mission_to_stage(i, m, language))
         .collect::<Vec<_>>();
     let mut table = Table::new(rows);
     table.with(Style::ascii());
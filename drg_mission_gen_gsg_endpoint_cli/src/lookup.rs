@@ -0,0 +1,168 @@
+//! `lookup` command: finds the seeds of Deep Dives matching a partial observation.
+
+use anyhow::bail;
+use serde::Serialize;
+use strum::VariantArray;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use drg_mission_gen_core::{
+    find_deep_dive_seeds, gen_deep_dive_pair, DeepDiveKind, DeepDiveObservation, EBiome,
+    EMissionTemplate, Language,
+};
+
+use crate::clean::{clean_unreal_deep_dive_with, map_biome, CleanMode};
+use crate::cleaned_deep_dive::{Biome, DeepDive};
+use crate::Format;
+
+#[derive(Debug, clap::Args)]
+pub struct LookupArgs {
+    /// Only match the normal or the elite Deep Dive.
+    #[arg(long, value_enum)]
+    pub(crate) kind: Option<Kind>,
+    /// Full codename, e.g. "Angry Overhang".
+    #[arg(long)]
+    pub(crate) name: Option<String>,
+    /// First word of the codename.
+    #[arg(long)]
+    pub(crate) first: Option<String>,
+    /// Last word of the codename.
+    #[arg(long)]
+    pub(crate) last: Option<String>,
+    #[arg(long, value_enum)]
+    pub(crate) biome: Option<Biome>,
+    /// Mission types of the stages in order, `any` for stages that are not known.
+    #[arg(long, value_enum, value_delimiter = ',', num_args = 1..=3)]
+    pub(crate) stages: Vec<StageMission>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, clap::ValueEnum)]
+pub(crate) enum Kind {
+    Normal,
+    Elite,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, clap::ValueEnum)]
+pub(crate) enum StageMission {
+    Any,
+    MiningExpedition,
+    PointExtraction,
+    EggHunt,
+    Elimination,
+    Salvage,
+    EscortDuty,
+    Refinery,
+    IndustrialSabotage,
+    DeepScan,
+    HeavyExtraction,
+}
+
+impl StageMission {
+    fn template(self) -> Option<EMissionTemplate> {
+        Some(match self {
+            StageMission::Any => return None,
+            StageMission::MiningExpedition => EMissionTemplate::MissionType_Extraction,
+            StageMission::PointExtraction => EMissionTemplate::MissionType_Motherlode,
+            StageMission::EggHunt => EMissionTemplate::MissionType_EggCollection,
+            StageMission::Elimination => EMissionTemplate::MissionType_Elimination,
+            StageMission::Salvage => EMissionTemplate::MissionType_Salvage,
+            StageMission::EscortDuty => EMissionTemplate::MissionType_Escort,
+            StageMission::Refinery => EMissionTemplate::MissionType_Refinery,
+            StageMission::IndustrialSabotage => EMissionTemplate::MissionType_Facility,
+            StageMission::DeepScan => EMissionTemplate::MissionType_DeepScan,
+            StageMission::HeavyExtraction => EMissionTemplate::MissionType_Excavation,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Found {
+    seed: u32,
+    kind: DeepDiveKind,
+    deep_dive: DeepDive,
+}
+
+#[derive(Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct Row {
+    seed: u32,
+    kind: &'static str,
+    codename: String,
+    biome: &'static str,
+    stages: String,
+}
+
+pub(crate) fn lookup(
+    args: &LookupArgs,
+    format: Format,
+    language: Language,
+    mode: CleanMode,
+) -> anyhow::Result<String> {
+    let mut stages = [None; 3];
+    for (stage, mission) in stages.iter_mut().zip(&args.stages) {
+        *stage = mission.template();
+    }
+    let observation = DeepDiveObservation {
+        kind: args.kind.map(|k| match k {
+            Kind::Normal => DeepDiveKind::Normal,
+            Kind::Elite => DeepDiveKind::Elite,
+        }),
+        name: args.name.clone(),
+        first_name: args.first.clone(),
+        last_name: args.last.clone(),
+        biome: args
+            .biome
+            .map(|b| *EBiome::VARIANTS.iter().find(|e| map_biome(e) == b).unwrap()),
+        stages,
+    };
+    if observation.name.is_none()
+        && observation.first_name.is_none()
+        && observation.last_name.is_none()
+        && observation.biome.is_none()
+        && observation.stages.iter().all(Option::is_none)
+    {
+        bail!(
+            "nothing to look up, pass at least one of --name, --first, --last, --biome or --stages"
+        );
+    }
+
+    let mut found = vec![];
+    for m in find_deep_dive_seeds(&observation)? {
+        let (normal, elite) = gen_deep_dive_pair(m.seed);
+        let dive = match m.kind {
+            DeepDiveKind::Normal => normal,
+            DeepDiveKind::Elite => elite,
+        };
+        found.push(Found {
+            seed: m.seed,
+            kind: m.kind,
+            deep_dive: clean_unreal_deep_dive_with(&dive, mode)?,
+        });
+    }
+
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&found)?,
+        Format::Plain | Format::Discord => {
+            let rows = found.iter().map(|f| Row {
+                seed: f.seed,
+                kind: match f.kind {
+                    DeepDiveKind::Normal => "Normal",
+                    DeepDiveKind::Elite => "Elite",
+                },
+                codename: f.deep_dive.name.clone(),
+                biome: f.deep_dive.biome.display_in(language),
+                stages: f
+                    .deep_dive
+                    .missions
+                    .iter()
+                    .map(|m| m.primary_objective.display_in(language))
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+            });
+            let mut table = Table::new(rows);
+            table.with(Style::ascii());
+            format!("{} matching Deep Dives\n\n{table}", found.len())
+        }
+        Format::Csv => return Err(format.unsupported("lookup")),
+    })
+}
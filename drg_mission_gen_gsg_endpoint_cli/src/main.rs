@@ -2,24 +2,72 @@ mod clean;
 mod cleaned_deep_dive;
 mod deep_dive_pair;
 mod deep_dive_response;
+mod draws;
+mod explain;
+mod filter;
 mod formatters;
 mod gsg_endpoint;
+mod lookup;
+mod record;
+mod search;
+mod stats;
+mod validate;
 
 use anyhow::Context;
-use clap::Parser;
-use clean::clean_unreal_deep_dive;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
+use clean::{clean_unreal_deep_dive_with, CleanMode};
 use deep_dive_pair::DeepDivePair;
-use drg_mission_gen_core::gen_deep_dive_pair;
+use drg_mission_gen_core::{try_gen_deep_dive_pair, Language};
 use tracing::*;
 
 use deep_dive_response::DeepDiveResponse;
 
 #[derive(Debug, Parser)]
 pub struct Args {
+    /// Shows this week's Deep Dives if no command is given.
+    #[command(subcommand)]
+    pub command: Option<Command>,
     /// What do you want the output format to be.
     #[clap(value_enum, default_value_t = Format::Json)]
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub format: Format,
+    /// Language of the biome, objective, mutator, warning and dreadnought names in plain and
+    /// Discord output. Translations are unofficial, codenames stay English.
+    #[clap(value_enum, default_value_t = Lang::En)]
+    #[arg(long, global = true)]
+    pub lang: Lang,
+    /// Fail on objectives, mutators and DNA that have no cleaned name. The default for every
+    /// command but the weekly Deep Dives.
+    #[arg(long, global = true, conflicts_with = "lenient")]
+    pub strict: bool,
+    /// Keep objectives, mutators and DNA that have no cleaned name as unknown, shown with their
+    /// internal name, and log a warning. The default for the weekly Deep Dives.
+    #[arg(long, global = true)]
+    pub lenient: bool,
+    /// Show how every choice of this week's Deep Dives, or of the given seed, was drawn instead of
+    /// the Deep Dives themselves. Can't be combined with a command.
+    #[arg(long, value_name = "SEED", num_args = 0..=1, value_parser = search::parse_seed)]
+    pub explain: Option<Option<u32>>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Find Deep Dive seeds from a codename, biome or stages, e.g. from a screenshot.
+    Lookup(lookup::LookupArgs),
+    /// Find Deep Dive seeds matching a filter expression.
+    Search(search::SearchArgs),
+    /// How often every objective, mutator, warning, biome and dreadnought appears over all seeds.
+    Stats,
+    /// Append this week's Deep Dive pair, or the pair of `--seed`, to the regression corpus once it
+    /// has been checked against the game.
+    Record(record::RecordArgs),
+    /// Check a Deep Dive from a JSON file against the generation rules and find the seeds that
+    /// generate it.
+    Validate(validate::ValidateArgs),
+    /// Log every random draw behind a Deep Dive pair, or find the first draw that differs from a
+    /// trace captured from the game.
+    Draws(draws::DrawsArgs),
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone, clap::ValueEnum)]
@@ -29,11 +77,111 @@ pub enum Format {
     Plain,
     /// Discord version which uses Discord emojis available in the main DRG Discord server.
     Discord,
+    /// Comma separated values, only for `stats`.
+    Csv,
+}
+
+impl Format {
+    /// Error for a command that has no output in this format.
+    pub(crate) fn unsupported(self, command: &str) -> anyhow::Error {
+        anyhow::anyhow!("{command} has no {self:?} output")
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, clap::ValueEnum)]
+pub enum Lang {
+    En,
+    De,
+    Ru,
+    /// Brazilian Portuguese.
+    PtBr,
+}
+
+impl From<Lang> for Language {
+    fn from(lang: Lang) -> Self {
+        match lang {
+            Lang::En => Language::English,
+            Lang::De => Language::German,
+            Lang::Ru => Language::Russian,
+            Lang::PtBr => Language::BrazilianPortuguese,
+        }
+    }
+}
+
+impl Args {
+    /// Cleaning mode picked with `--strict` or `--lenient`, `default` if neither is given.
+    fn clean_mode(&self, default: CleanMode) -> CleanMode {
+        if self.strict {
+            CleanMode::Strict
+        } else if self.lenient {
+            CleanMode::Lenient
+        } else {
+            default
+        }
+    }
 }
 
 pub fn main() -> anyhow::Result<()> {
     drg_mission_gen_tracing::setup_logging();
 
+    let args = Args::parse();
+    if args.explain.is_some() && args.command.is_some() {
+        // clap can't make an argument conflict with the subcommands while keeping the global ones
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--explain can't be used with a command",
+            )
+            .exit();
+    }
+    if args.lang != Lang::En {
+        warn!(
+            "--lang translations are unofficial, written by hand rather than taken from the \
+             game, and can differ from the names shown in game"
+        );
+    }
+    let output = match &args.command {
+        None => match args.explain {
+            Some(Some(seed)) => explain::explain(seed, args.format)?,
+            Some(None) => {
+                let response = gsg_endpoint::query_gsg_deep_dive_endpoint()
+                    .context("querying GSG deep dive endpoint")?;
+                explain::explain(response.seed_v2 as u32, args.format)?
+            }
+            None => weekly(
+                args.format,
+                args.lang.into(),
+                args.clean_mode(CleanMode::Lenient),
+            )?,
+        },
+        Some(Command::Lookup(lookup)) => lookup::lookup(
+            lookup,
+            args.format,
+            args.lang.into(),
+            args.clean_mode(CleanMode::Strict),
+        )?,
+        Some(Command::Search(search)) => search::search(
+            search,
+            args.format,
+            args.lang.into(),
+            args.clean_mode(CleanMode::Strict),
+        )?,
+        Some(Command::Stats) => stats::stats(
+            args.format,
+            args.lang.into(),
+            args.clean_mode(CleanMode::Strict),
+        )?,
+        Some(Command::Record(record)) => record::record(record)?,
+        Some(Command::Validate(validate)) => validate::validate(validate, args.format)?,
+        Some(Command::Draws(draws)) => draws::draws(draws, args.format)?,
+    };
+
+    println!("{}", output);
+
+    Ok(())
+}
+
+fn weekly(format: Format, language: Language, mode: CleanMode) -> anyhow::Result<String> {
     let ref deep_dive_response @ DeepDiveResponse {
         seed_v2,
         ref expiration_datetime,
@@ -48,13 +196,14 @@ pub fn main() -> anyhow::Result<()> {
         expiration_date=%expiration_date
     );
 
-    let (normal_deep_dive, elite_deep_dive) = gen_deep_dive_pair(seed_v2 as u32);
+    let (normal_deep_dive, elite_deep_dive) =
+        try_gen_deep_dive_pair(seed_v2 as u32).context("generating deep dives")?;
     debug!(?normal_deep_dive);
     debug!(?elite_deep_dive);
 
     let (normal_deep_dive, elite_deep_dive) = (
-        clean_unreal_deep_dive(&normal_deep_dive)?,
-        clean_unreal_deep_dive(&elite_deep_dive)?,
+        clean_unreal_deep_dive_with(&normal_deep_dive, mode)?,
+        clean_unreal_deep_dive_with(&elite_deep_dive, mode)?,
     );
     debug!(?normal_deep_dive);
     debug!(?elite_deep_dive);
@@ -63,23 +212,22 @@ pub fn main() -> anyhow::Result<()> {
         elite: elite_deep_dive,
     };
 
-    let args = Args::parse();
-
-    let formatted_deep_dive = match args.format {
+    let formatted_deep_dive = match format {
         Format::Json => serde_json::to_string_pretty(&deep_dive_pair)?,
         Format::Plain => formatters::plain::format_plain(
             &deep_dive_pair,
             release_datetime,
             **expiration_datetime,
+            language,
         ),
+        Format::Csv => return Err(format.unsupported("the weekly Deep Dives")),
         Format::Discord => formatters::discord::format_discord(
             &deep_dive_pair,
             release_datetime,
             **expiration_datetime,
+            language,
         ),
     };
 
-    println!("{}", formatted_deep_dive);
-
-    Ok(())
+    Ok(formatted_deep_dive)
 }
@@ -0,0 +1,235 @@
+//! `record` command: appends a Deep Dive pair seen in game to the regression corpus.
+//!
+//! The corpus is `drg_mission_gen_core/test_data/dds.json`. Pairs recorded here hold the seed and
+//! the stages as shown in game, never the generator's own output. A pair is recorded even when
+//! [`gen_deep_dive_pair`] does not reproduce it, the `compare_corpus` test then fails until the
+//! generator is fixed.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+use drg_mission_gen_core::gen_deep_dive_pair;
+
+use crate::clean::clean_unreal_deep_dive;
+use crate::gsg_endpoint;
+use crate::search::parse_seed;
+use crate::validate::Input;
+
+#[derive(Debug, clap::Args)]
+pub struct RecordArgs {
+    /// JSON file with the normal dive as seen in game, in the format `validate` reads.
+    #[arg(long)]
+    pub(crate) normal: PathBuf,
+    /// JSON file with the elite dive as seen in game, in the format `validate` reads.
+    #[arg(long)]
+    pub(crate) elite: PathBuf,
+    /// Seed of the observed week instead of this week's seed from the GSG endpoint.
+    #[arg(long, value_parser = parse_seed)]
+    pub(crate) seed: Option<u32>,
+    /// Release date of the week the pair was seen in game, e.g. 2024-07-11. Filled in from the
+    /// endpoint when `--seed` is not given.
+    #[arg(long, requires = "seed")]
+    pub(crate) week: Option<String>,
+    /// Corpus file to append to, usually `drg_mission_gen_core/test_data/dds.json`.
+    #[arg(long)]
+    pub(crate) corpus: PathBuf,
+}
+
+/// An entry of the corpus. Entries written by the generator itself hold both dives as
+/// [`Input::Unreal`], the core crate's `compare_dds` test checks those field by field.
+#[derive(Debug, Serialize, Deserialize)]
+struct CorpusEntry {
+    seed: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    week: Option<String>,
+    normal: Input,
+    hard: Input,
+}
+
+pub(crate) fn record(args: &RecordArgs) -> anyhow::Result<String> {
+    let normal = Input::read(&args.normal)?;
+    let hard = Input::read(&args.elite)?;
+    let (seed, week) = match args.seed {
+        Some(seed) => (seed, args.week.clone()),
+        None => {
+            let response = gsg_endpoint::query_gsg_deep_dive_endpoint()
+                .context("querying GSG deep dive endpoint")?;
+            let week = response
+                .expiration_datetime
+                .release_datetime()
+                .date()
+                .to_string();
+            (response.seed_v2 as u32, Some(week))
+        }
+    };
+
+    let mut corpus: Vec<CorpusEntry> = if args.corpus.exists() {
+        let bytes = std::fs::read(&args.corpus)
+            .with_context(|| format!("reading {}", args.corpus.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("parsing {}", args.corpus.display()))?
+    } else {
+        vec![]
+    };
+    if corpus.iter().any(|e| e.seed == seed) {
+        bail!("seed {seed} is already in {}", args.corpus.display());
+    }
+
+    let entry = CorpusEntry {
+        seed,
+        week,
+        normal,
+        hard,
+    };
+    let differences = entry.differences()?;
+    let mut summary = format!(
+        "recorded seed {seed}{} ({} / {})",
+        entry
+            .week
+            .as_ref()
+            .map(|week| format!(" of week {week}"))
+            .unwrap_or_default(),
+        entry.normal.cleaned()?.name,
+        entry.hard.cleaned()?.name
+    );
+    corpus.push(entry);
+    std::fs::write(&args.corpus, serde_json::to_string_pretty(&corpus)? + "\n")
+        .with_context(|| format!("writing {}", args.corpus.display()))?;
+    summary += &format!(" in {}", args.corpus.display());
+    if !differences.is_empty() {
+        summary += &format!(
+            "\nwarning: the generator does not reproduce the observed dives, compare_corpus will \
+             fail:\n  {}",
+            differences.join("\n  ")
+        );
+    }
+    Ok(summary)
+}
+
+impl CorpusEntry {
+    /// Where the generated pair differs from the observed one. The seed of a cleaned dive is not
+    /// compared, it is not shown in game.
+    fn differences(&self) -> anyhow::Result<Vec<String>> {
+        let (normal, hard) = gen_deep_dive_pair(self.seed);
+        let mut differences = vec![];
+        for (kind, observed, generated) in [
+            (
+                "normal",
+                self.normal.cleaned()?,
+                clean_unreal_deep_dive(&normal)?,
+            ),
+            (
+                "elite",
+                self.hard.cleaned()?,
+                clean_unreal_deep_dive(&hard)?,
+            ),
+        ] {
+            if observed.name != generated.name {
+                differences.push(format!(
+                    "{kind} name: observed {:?}, generated {:?}",
+                    observed.name, generated.name
+                ));
+            }
+            if observed.biome != generated.biome {
+                differences.push(format!(
+                    "{kind} biome: observed {:?}, generated {:?}",
+                    observed.biome, generated.biome
+                ));
+            }
+            if observed.missions.len() != generated.missions.len() {
+                differences.push(format!(
+                    "{kind}: observed {} stages, generated {}",
+                    observed.missions.len(),
+                    generated.missions.len()
+                ));
+            }
+            for (i, (observed, generated)) in observed
+                .missions
+                .iter()
+                .zip(&generated.missions)
+                .enumerate()
+            {
+                if observed != generated {
+                    differences.push(format!(
+                        "{kind} stage {}: observed {observed:?}, generated {generated:?}",
+                        i + 1
+                    ));
+                }
+            }
+        }
+        Ok(differences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORPUS: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../drg_mission_gen_core/test_data/dds.json"
+    );
+
+    #[test]
+    fn compare_corpus() {
+        let corpus: Vec<CorpusEntry> =
+            serde_json::from_slice(&std::fs::read(CORPUS).unwrap()).unwrap();
+        assert!(!corpus.is_empty());
+        for entry in corpus {
+            println!("seed = {}, week = {:?}", entry.seed, entry.week);
+            assert_eq!(entry.differences().unwrap(), Vec::<String>::new());
+        }
+    }
+
+    #[test]
+    fn test_differences() {
+        let (normal, hard) = gen_deep_dive_pair(216);
+        let mut hard = clean_unreal_deep_dive(&hard).unwrap();
+        let entry = CorpusEntry {
+            seed: 216,
+            week: None,
+            normal: Input::Unreal(normal),
+            hard: Input::Cleaned(hard.clone()),
+        };
+        assert_eq!(entry.differences().unwrap(), Vec::<String>::new());
+
+        hard.missions.swap(0, 2);
+        let entry = CorpusEntry {
+            hard: Input::Cleaned(hard),
+            ..entry
+        };
+        assert_eq!(entry.differences().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_record_mismatch() {
+        let dir = std::env::temp_dir().join(format!("record-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (normal, hard) = gen_deep_dive_pair(216);
+        let mut hard = clean_unreal_deep_dive(&hard).unwrap();
+        hard.missions.swap(0, 2);
+        std::fs::write(
+            dir.join("normal.json"),
+            serde_json::to_string(&normal).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(dir.join("hard.json"), serde_json::to_string(&hard).unwrap()).unwrap();
+        let args = RecordArgs {
+            normal: dir.join("normal.json"),
+            elite: dir.join("hard.json"),
+            seed: Some(216),
+            week: None,
+            corpus: dir.join("dds.json"),
+        };
+
+        let summary = record(&args).unwrap();
+        assert!(summary.contains("warning"), "{summary}");
+        let corpus: Vec<CorpusEntry> =
+            serde_json::from_slice(&std::fs::read(&args.corpus).unwrap()).unwrap();
+        assert_eq!(corpus.len(), 1);
+        assert_eq!(corpus[0].differences().unwrap().len(), 2);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
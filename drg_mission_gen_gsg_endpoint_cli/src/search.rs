@@ -0,0 +1,111 @@
+//! `search` command: finds the Deep Dive seeds whose pair matches a filter expression.
+
+use anyhow::{bail, Context};
+use serde::Serialize;
+
+use drg_mission_gen_core::{
+    gen_deep_dive_pair, search_deep_dive_seeds, Language, DEEP_DIVE_SEED_COUNT,
+};
+
+use crate::clean::{clean_unreal_deep_dive_with, CleanMode};
+use crate::deep_dive_pair::DeepDivePair;
+use crate::filter::Filter;
+use crate::formatters::discord::format_dive;
+use crate::formatters::plain::format_plain_dd;
+use crate::Format;
+
+#[derive(Debug, Serialize)]
+struct Found {
+    seed: u32,
+    #[serde(flatten)]
+    pair: DeepDivePair,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SearchArgs {
+    /// Filter expression, e.g.
+    /// `elite.stage[1].primary == Escort && elite.biome == AzureWeald && !any(secondary == Blackbox)`.
+    pub(crate) filter: String,
+    /// First seed to check.
+    #[arg(long, default_value = "0", value_parser = parse_seed)]
+    pub(crate) from: u32,
+    /// Seed to stop at, not checked itself. Only the lower 17 bits of a seed are used, so every
+    /// distinct pair is below 0x20000.
+    #[arg(long, default_value = "0x20000", value_parser = parse_seed)]
+    pub(crate) to: u32,
+    /// Only show the first matches.
+    #[arg(long)]
+    pub(crate) limit: Option<usize>,
+}
+
+/// Parses a decimal or `0x` prefixed hexadecimal seed.
+pub(crate) fn parse_seed(seed: &str) -> Result<u32, std::num::ParseIntError> {
+    match seed.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => seed.parse(),
+    }
+}
+
+pub(crate) fn search(
+    args: &SearchArgs,
+    format: Format,
+    language: Language,
+    mode: CleanMode,
+) -> anyhow::Result<String> {
+    let filter = Filter::parse(&args.filter).context("parsing filter")?;
+    if args.from >= args.to {
+        bail!("empty seed range {}..{}", args.from, args.to);
+    }
+    if args.to > DEEP_DIVE_SEED_COUNT {
+        tracing::warn!("seeds from {DEEP_DIVE_SEED_COUNT:#x} on repeat earlier Deep Dives");
+    }
+
+    let mut seeds = search_deep_dive_seeds(args.from..args.to, |pair| filter.matches(pair));
+    if let Some(limit) = args.limit {
+        seeds.truncate(limit);
+    }
+    let mut found = vec![];
+    for seed in seeds {
+        let (normal, elite) = gen_deep_dive_pair(seed);
+        found.push(Found {
+            seed,
+            pair: DeepDivePair {
+                normal: clean_unreal_deep_dive_with(&normal, mode)?,
+                elite: clean_unreal_deep_dive_with(&elite, mode)?,
+            },
+        });
+    }
+
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&found)?,
+        Format::Plain => {
+            let mut out = format!("{} matching seeds\n", found.len());
+            for Found { seed, pair } in &found {
+                out += &format!(
+                    "\n=== Seed {} ===\n\n=== Normal Deep Dive ===\n{}\n\n=== Elite Deep Dive ===\n{}\n",
+                    seed,
+                    format_plain_dd(&pair.normal, language),
+                    format_plain_dd(&pair.elite, language),
+                );
+            }
+            out
+        }
+        Format::Discord => found
+            .iter()
+            .map(|Found { seed, pair }| {
+                format!(
+                    "Seed **{}**\n\
+                    :Deep_Dive: __**DEEP DIVE**__ :Deep_Dive:\n\
+                    {}\n\n\
+                    :Deep_Dive: __**ELITE DEEP DIVE**__ :Deep_Dive:\n\
+                    {}\n",
+                    seed,
+                    format_dive(&pair.normal, language),
+                    format_dive(&pair.elite, language),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Format::Csv => return Err(format.unsupported("search")),
+    })
+}
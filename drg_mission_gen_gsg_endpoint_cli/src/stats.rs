@@ -0,0 +1,246 @@
+//! `stats` command: how often every feature appears over the whole seed space.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use tabled::settings::Style;
+use tabled::{Table, Tabled};
+
+use drg_mission_gen_core::{
+    gen_deep_dive_pair, EDreadnought, EObjective, Language, DEEP_DIVE_SEED_COUNT,
+};
+
+use crate::clean::{clean_unreal_deep_dive_with, CleanMode};
+use crate::cleaned_deep_dive::{
+    Biome, Complexity, DeepDive, DeepDiveSecondaryObjective, Duration, Mutator, PrimaryObjective,
+    Warning,
+};
+use crate::Format;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+enum Dive {
+    Normal,
+    Elite,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+enum Feature {
+    Biome,
+    Primary,
+    /// Duration and complexity of the mission.
+    Dna,
+    Secondary,
+    Mutator,
+    Warning,
+    /// Targets of Elimination primaries and Dreadnought secondaries.
+    Dreadnought,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct Row {
+    #[tabled(display_with = "display_debug")]
+    dive: Dive,
+    /// 1 to 3, `None` for features of the whole dive.
+    #[tabled(display_with = "display_stage")]
+    stage: Option<usize>,
+    #[tabled(display_with = "display_debug")]
+    feature: Feature,
+    value: String,
+    /// Seeds the value appears for.
+    count: u32,
+    /// `count` over the number of seeds.
+    #[tabled(display_with = "display_share")]
+    share: f64,
+}
+
+fn display_debug(value: &impl std::fmt::Debug) -> String {
+    format!("{value:?}")
+}
+
+fn display_stage(stage: &Option<usize>) -> String {
+    stage.map(|s| s.to_string()).unwrap_or_default()
+}
+
+fn display_share(share: &f64) -> String {
+    format!("{:.3}%", share * 100.0)
+}
+
+pub(crate) fn stats(format: Format, language: Language, mode: CleanMode) -> anyhow::Result<String> {
+    let mut counts: BTreeMap<(Dive, Feature, Option<usize>, Value), u32> = BTreeMap::new();
+    for seed in 0..DEEP_DIVE_SEED_COUNT {
+        let (normal, elite) = gen_deep_dive_pair(seed);
+        for (dive, deep_dive) in [(Dive::Normal, normal), (Dive::Elite, elite)] {
+            let deep_dive = clean_unreal_deep_dive_with(&deep_dive, mode)?;
+            for (feature, stage, value) in features(&deep_dive) {
+                *counts.entry((dive, feature, stage, value)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut rows: Vec<_> = counts
+        .into_iter()
+        .map(|((dive, feature, stage, value), count)| Row {
+            dive,
+            stage,
+            feature,
+            value: value.display_in(language),
+            count,
+            share: count as f64 / DEEP_DIVE_SEED_COUNT as f64,
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        (a.dive, a.feature, a.stage, b.count).cmp(&(b.dive, b.feature, b.stage, a.count))
+    });
+
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&rows)?,
+        Format::Plain | Format::Discord => {
+            let mut table = Table::new(&rows);
+            table.with(Style::ascii());
+            format!("Frequencies over all {DEEP_DIVE_SEED_COUNT} Deep Dive seeds\n\n{table}")
+        }
+        Format::Csv => to_csv(&rows),
+    })
+}
+
+/// Value of a feature, counted as is and only turned into text for the output.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Value {
+    Biome(Biome),
+    /// Eliminations without their targets, those are counted as [`Feature::Dreadnought`].
+    Primary(PrimaryObjective),
+    Dna(Duration, Complexity),
+    /// Dreadnought secondaries without their targets.
+    Secondary(DeepDiveSecondaryObjective),
+    Mutator(Option<Mutator>),
+    Warning(Option<Warning>),
+    Dreadnought(EDreadnought),
+}
+
+impl Value {
+    fn display_in(&self, language: Language) -> String {
+        match self {
+            Value::Biome(biome) => biome.display_in(language).to_string(),
+            Value::Primary(primary) => primary.display_in(language).to_string(),
+            Value::Dna(duration, complexity) => {
+                format!("{} / {}", <&str>::from(duration), <&str>::from(complexity))
+            }
+            Value::Secondary(DeepDiveSecondaryObjective::Dreadnought { .. }) => {
+                EObjective::OBJ_DD_Elimination_Eggs
+                    .display_name_in(language)
+                    .to_string()
+            }
+            Value::Secondary(secondary) => secondary.display_in(language),
+            Value::Mutator(mutator) => mutator
+                .map_or(language.none(), |m| m.display_in(language))
+                .to_string(),
+            Value::Warning(warning) => warning
+                .map_or(language.none(), |w| w.display_in(language))
+                .to_string(),
+            Value::Dreadnought(dreadnought) => dreadnought.display_name_in(language).to_string(),
+        }
+    }
+}
+
+/// Every feature of a dive with the stage it appears in, `None` for the biome.
+fn features(dive: &DeepDive) -> Vec<(Feature, Option<usize>, Value)> {
+    let mut features = vec![(Feature::Biome, None, Value::Biome(dive.biome))];
+    for (i, mission) in dive.missions.iter().enumerate() {
+        let stage = Some(i + 1);
+        let primary = match &mission.primary_objective {
+            PrimaryObjective::Elimination { .. } => {
+                PrimaryObjective::Elimination { targets: vec![] }
+            }
+            primary => primary.clone(),
+        };
+        features.push((Feature::Primary, stage, Value::Primary(primary)));
+        features.push((
+            Feature::Dna,
+            stage,
+            Value::Dna(mission.duration, mission.complexity),
+        ));
+        let secondary = match &mission.secondary_objective {
+            DeepDiveSecondaryObjective::Dreadnought { .. } => {
+                DeepDiveSecondaryObjective::Dreadnought { targets: vec![] }
+            }
+            secondary => secondary.clone(),
+        };
+        features.push((Feature::Secondary, stage, Value::Secondary(secondary)));
+        features.push((Feature::Mutator, stage, Value::Mutator(mission.mutator)));
+        features.push((Feature::Warning, stage, Value::Warning(mission.warning)));
+
+        let primary_targets = match &mission.primary_objective {
+            PrimaryObjective::Elimination { targets } => targets.as_slice(),
+            _ => &[],
+        };
+        let secondary_targets = match &mission.secondary_objective {
+            DeepDiveSecondaryObjective::Dreadnought { targets } => targets.as_slice(),
+            _ => &[],
+        };
+        for target in primary_targets.iter().chain(secondary_targets) {
+            features.push((Feature::Dreadnought, stage, Value::Dreadnought(*target)));
+        }
+    }
+    features.sort();
+    features.dedup();
+    features
+}
+
+fn to_csv(rows: &[Row]) -> String {
+    let field = |s: String| {
+        if s.contains([',', '"', '\n']) {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        } else {
+            s
+        }
+    };
+    let mut csv = "dive,stage,feature,value,count,share\n".to_string();
+    for row in rows {
+        csv += &format!(
+            "{:?},{},{:?},{},{},{}\n",
+            row.dive,
+            display_stage(&row.stage),
+            row.feature,
+            field(row.value.clone()),
+            row.count,
+            row.share
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clean::clean_unreal_deep_dive;
+
+    #[test]
+    fn test_features() {
+        let (_, elite) = gen_deep_dive_pair(216);
+        let features = features(&clean_unreal_deep_dive(&elite).unwrap());
+        for feature in [
+            Feature::Primary,
+            Feature::Dna,
+            Feature::Secondary,
+            Feature::Mutator,
+        ] {
+            for stage in 1..=3 {
+                let values = features
+                    .iter()
+                    .filter(|(f, s, _)| *f == feature && *s == Some(stage))
+                    .count();
+                assert_eq!(values, 1, "{feature:?} of stage {stage}");
+            }
+        }
+        assert!(features.contains(&(Feature::Biome, None, Value::Biome(Biome::AzureWeald))));
+        assert!(features.contains(&(
+            Feature::Primary,
+            Some(1),
+            Value::Primary(PrimaryObjective::EscortDuty)
+        )));
+        assert!(features.contains(&(Feature::Mutator, Some(1), Value::Mutator(None))));
+        assert_eq!(Value::Mutator(None).display_in(Language::English), "None");
+        assert_eq!(Value::Mutator(None).display_in(Language::German), "Keine");
+    }
+}
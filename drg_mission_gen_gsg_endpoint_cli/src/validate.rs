@@ -0,0 +1,248 @@
+//! `validate` command: checks a Deep Dive from elsewhere, e.g. a community spreadsheet, against the
+//! generation rules and finds the seeds that produce it.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+
+use drg_mission_gen_core::{
+    deep_dive_violations, search_deep_dive_seeds, validate_deep_dive, DeepDiveKind,
+    DeepDiveValidation, EBiome, EObjective, GameVersion, ObjectiveInstance, UDeepDive,
+    UGeneratedMission, DEEP_DIVE_SEED_COUNT,
+};
+
+use crate::clean::{clean_unreal_deep_dive, find_dna, map_biome, map_mission};
+use crate::cleaned_deep_dive::{
+    DeepDive, DeepDiveSecondaryObjective, Mission, PrimaryObjective, Warning,
+};
+use crate::lookup::Kind;
+use crate::Format;
+
+#[derive(Debug, clap::Args)]
+pub struct ValidateArgs {
+    /// JSON file with a single Deep Dive, either as generated by the core crate or as printed by
+    /// `--format=json`.
+    pub(crate) path: PathBuf,
+    /// Whether the dive is the normal or the elite dive of its pair.
+    #[arg(long, value_enum)]
+    pub(crate) kind: Kind,
+}
+
+/// A Deep Dive read from a file, as accepted by `validate` and `record`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Input {
+    Unreal(UDeepDive),
+    Cleaned(DeepDive),
+}
+
+impl Input {
+    pub(crate) fn read(path: &Path) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("parsing {} as a Deep Dive", path.display()))
+    }
+
+    /// The dive as shown in game.
+    pub(crate) fn cleaned(&self) -> anyhow::Result<DeepDive> {
+        Ok(match self {
+            Input::Unreal(dive) => clean_unreal_deep_dive(dive)?,
+            Input::Cleaned(dive) => dive.clone(),
+        })
+    }
+}
+
+pub(crate) fn validate(args: &ValidateArgs, format: Format) -> anyhow::Result<String> {
+    let input = Input::read(&args.path)?;
+    let kind = match args.kind {
+        Kind::Normal => DeepDiveKind::Normal,
+        Kind::Elite => DeepDiveKind::Elite,
+    };
+
+    let validation = match input {
+        Input::Unreal(dive) => validate_deep_dive(kind, &dive),
+        Input::Cleaned(dive) => validate_cleaned(kind, &dive)?,
+    };
+
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(&validation)?,
+        Format::Plain | Format::Discord => {
+            let mut out = match validation.violations.len() {
+                0 => "No rule is broken\n".to_string(),
+                n => format!("{n} broken rules\n"),
+            };
+            for violation in &validation.violations {
+                match violation.stage {
+                    Some(stage) => out += &format!("  Stage {}: {}\n", stage + 1, violation.rule),
+                    None => out += &format!("  {}\n", violation.rule),
+                }
+            }
+            out += &match validation.seeds.as_slice() {
+                [] => "\nNo seed generates this Deep Dive".to_string(),
+                seeds => format!(
+                    "\nGenerated by seeds {}",
+                    seeds
+                        .iter()
+                        .map(u32::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            out
+        }
+        Format::Csv => return Err(format.unsupported("validate")),
+    })
+}
+
+/// Checks a cleaned dive. Rules are checked on the [`UDeepDive`] it most likely came from, while
+/// seeds are matched on the cleaned dive itself since cleaning drops the DNA and the mission seeds.
+/// The seed of the cleaned dive is not compared, spreadsheets rarely have it.
+fn validate_cleaned(kind: DeepDiveKind, dive: &DeepDive) -> anyhow::Result<DeepDiveValidation> {
+    let data = GameVersion::LATEST.data();
+    let unreal = uncleaned_deep_dive(kind, dive)?;
+    let violations = deep_dive_violations(data, kind, &unreal);
+    let seeds = if dive.missions.len() == 3 {
+        search_deep_dive_seeds(0..DEEP_DIVE_SEED_COUNT, |pair| {
+            map_biome(&pair.biome(kind)) == dive.biome
+                && pair.name(kind) == dive.name
+                && (0..3)
+                    .all(|i| map_mission(pair.stage(kind, i)).is_ok_and(|m| m == dive.missions[i]))
+        })
+    } else {
+        vec![]
+    };
+    Ok(DeepDiveValidation { violations, seeds })
+}
+
+fn uncleaned_deep_dive(kind: DeepDiveKind, dive: &DeepDive) -> anyhow::Result<UDeepDive> {
+    let biome = dive.biome.unreal();
+    let missions = dive
+        .missions
+        .iter()
+        .enumerate()
+        .map(|(i, mission)| {
+            uncleaned_mission(kind, biome, mission).with_context(|| format!("stage {}", i + 1))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(UDeepDive {
+        name: dive.name.clone(),
+        biome,
+        missions,
+    })
+}
+
+/// Picks the DNA and the duration or complexity limit the Deep Dive template would have given the
+/// stage. A limit no template item allows is kept as a duration limit so that it is reported.
+fn uncleaned_mission(
+    kind: DeepDiveKind,
+    biome: EBiome,
+    mission: &Mission,
+) -> anyhow::Result<UGeneratedMission> {
+    let data = GameVersion::LATEST.data();
+    let template = mission
+        .primary_objective
+        .template()
+        .map_err(|name| anyhow!("unknown primary objective `{name}`"))?;
+    let Some(dna) = find_dna(template, mission.complexity, mission.duration) else {
+        bail!(
+            "no DNA of {template:?} is {:?} and {:?}",
+            mission.duration,
+            mission.complexity
+        );
+    };
+    let (duration, complexity) = (dna.get().duration, dna.get().complexity);
+
+    let deep_dive_template = match kind {
+        DeepDiveKind::Normal => data.normal_template,
+        DeepDiveKind::Elite => data.hard_template,
+    };
+    let items = deep_dive_template
+        .missions
+        .iter()
+        .filter(|item| item.mission == template);
+    let mut limits = (Some(duration), None);
+    for item in items {
+        if item.allowed_durations.contains(&duration) {
+            limits = (Some(duration), None);
+            break;
+        } else if item.allowed_durations.is_empty() {
+            if item.allowed_complexities.contains(&complexity) {
+                limits = (None, Some(complexity));
+                break;
+            } else if item.allowed_complexities.is_empty() {
+                limits = (None, None);
+                break;
+            }
+        }
+    }
+
+    Ok(UGeneratedMission {
+        seed: 0,
+        template,
+        biome,
+        primary_objective: match &mission.primary_objective {
+            PrimaryObjective::Elimination { targets } => ObjectiveInstance::Elimination {
+                kind: EObjective::OBJ_Eliminate_Eggs,
+                targets: targets.clone(),
+            },
+            _ => ObjectiveInstance::Other {
+                kind: template.get().mission_template.primary_objective,
+            },
+        },
+        secondary_objectives: vec![secondary_objective(&mission.secondary_objective)?],
+        mutators: mission
+            .mutator
+            .map(|mutator| {
+                mutator
+                    .unreal()
+                    .map_err(|name| anyhow!("unknown mutator `{name}`"))
+            })
+            .transpose()?
+            .into_iter()
+            .collect(),
+        warnings: mission.warning.map(Warning::unreal).into_iter().collect(),
+        complexity_limit: limits.1,
+        duration_limit: limits.0,
+        dna,
+    })
+}
+
+fn secondary_objective(
+    objective: &DeepDiveSecondaryObjective,
+) -> anyhow::Result<ObjectiveInstance> {
+    let kind = objective
+        .objective()
+        .map_err(|name| anyhow!("unknown secondary objective `{name}`"))?;
+    Ok(match objective {
+        DeepDiveSecondaryObjective::Dreadnought { targets } => ObjectiveInstance::Elimination {
+            kind,
+            targets: targets.clone(),
+        },
+        _ => ObjectiveInstance::Other { kind },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use drg_mission_gen_core::gen_deep_dive_pair;
+
+    use super::*;
+    use crate::cleaned_deep_dive::Mutator;
+
+    #[test]
+    fn test_validate_cleaned() {
+        let (normal, _) = gen_deep_dive_pair(216);
+        let mut dive = clean_unreal_deep_dive(&normal).unwrap();
+        let validation = validate_cleaned(DeepDiveKind::Normal, &dive).unwrap();
+        assert_eq!(validation.violations, vec![]);
+        assert!(validation.seeds.contains(&216));
+
+        for mission in &mut dive.missions {
+            mission.mutator = Some(Mutator::LowGravity);
+        }
+        let validation = validate_cleaned(DeepDiveKind::Normal, &dive).unwrap();
+        assert!(!validation.is_valid());
+        assert!(validation.seeds.is_empty());
+    }
+}